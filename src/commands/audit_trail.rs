@@ -0,0 +1,74 @@
+use crate::manifest::{self, MANIFEST_PATH};
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+
+    let mut keys: Vec<String> = manifest::dependency_tables(&doc)
+        .iter()
+        .flat_map(|(_, table)| table.iter().map(|(key, _)| key.to_string()))
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    if keys.is_empty() {
+        println!("No dependencies declared in Cargo.toml.");
+        return Ok(());
+    }
+
+    for key in &keys {
+        match first_commit(key)? {
+            Some(commit) => println!(
+                "{} — added by {} <{}> on {} in commit {}",
+                key, commit.author, commit.email, commit.date, commit.hash
+            ),
+            None => println!("{} — not found in git history (uncommitted?)", key),
+        }
+    }
+
+    Ok(())
+}
+
+struct Commit {
+    hash: String,
+    author: String,
+    email: String,
+    date: String,
+}
+
+/// The oldest commit whose Cargo.toml diff touches a declaration of `key`,
+/// either as `key = ...` or as a `[dependencies.key]` table.
+fn first_commit(key: &str) -> Result<Option<Commit>, Box<dyn std::error::Error>> {
+    let pattern = format!(
+        r"^[[:space:]]*{0}[[:space:]]*=|dependencies\.{0}\]",
+        key.replace('.', r"\.")
+    );
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--reverse",
+            "--date=short",
+            "--format=%h%x09%an%x09%ae%x09%ad",
+        ])
+        .arg(format!("-G{}", pattern))
+        .args(["--", MANIFEST_PATH])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git log failed: {}", stderr.trim()).into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let commit = stdout.lines().next().and_then(|line| {
+        let mut fields = line.split('\t');
+        Some(Commit {
+            hash: fields.next()?.to_string(),
+            author: fields.next()?.to_string(),
+            email: fields.next()?.to_string(),
+            date: fields.next()?.to_string(),
+        })
+    });
+
+    Ok(commit)
+}
@@ -0,0 +1,143 @@
+use crate::commands::confirm;
+use crate::{is_std_module, manifest, source};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+use toml_edit::{Item, Table};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let cfg_regex = Regex::new(r"^#\[cfg\((.*(?:target_arch|target_pointer_width).*)\)\]$")?;
+    let use_regex = Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?use\s+(?:::)?([a-zA-Z_][a-zA-Z0-9_]*)")?;
+
+    // Crate -> cfg conditions its `use` statements appear under
+    let mut arch_uses: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut plain_uses: HashSet<String> = HashSet::new();
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        let mut pending_cfg: Option<String> = None;
+
+        for line in content.lines().map(str::trim) {
+            if let Some(cap) = cfg_regex.captures(line) {
+                pending_cfg = Some(cap[1].to_string());
+                continue;
+            }
+            if line.starts_with("#[") || line.is_empty() {
+                continue;
+            }
+            if let Some(cap) = use_regex.captures(line) {
+                let name = cap[1].to_string();
+                if !is_std_module(&name) && !["crate", "self", "super"].contains(&name.as_str()) {
+                    match &pending_cfg {
+                        Some(cfg) => {
+                            arch_uses.entry(name).or_default().insert(cfg.clone());
+                        }
+                        None => {
+                            plain_uses.insert(name);
+                        }
+                    }
+                }
+            }
+            pending_cfg = None;
+        }
+    }
+    arch_uses.retain(|name, _| !plain_uses.contains(name));
+
+    if arch_uses.is_empty() {
+        println!("No architecture-specific `use` statements found.");
+        return Ok(());
+    }
+
+    let mut doc = manifest::read_manifest()?;
+    let plain: Vec<String> = doc
+        .get("dependencies")
+        .and_then(Item::as_table_like)
+        .map(|table| table.iter().map(|(key, _)| key.replace('-', "_")).collect())
+        .unwrap_or_default();
+    let declared: Vec<String> = manifest::dependency_tables(&doc)
+        .iter()
+        .flat_map(|(_, table)| table.iter().map(|(key, _)| key.replace('-', "_")))
+        .collect();
+
+    let mut misplaced = Vec::new();
+    for (name, cfgs) in &arch_uses {
+        let cfg = if cfgs.len() == 1 {
+            format!(
+                "cfg({})",
+                cfgs.iter().next().map(String::as_str).unwrap_or_default()
+            )
+        } else {
+            format!(
+                "cfg(any({}))",
+                cfgs.iter().cloned().collect::<Vec<_>>().join(", ")
+            )
+        };
+
+        if plain.contains(name) {
+            println!(
+                "✗ {} is only used under {} but is in the platform-independent [dependencies]",
+                name, cfg
+            );
+            misplaced.push((name.clone(), cfg));
+        } else if !declared.contains(name) {
+            println!(
+                "✗ {} is used under {} but isn't declared; add it to [target.'{}'.dependencies]",
+                name, cfg, cfg
+            );
+        } else {
+            println!("✓ {} is declared in a target-specific section", name);
+        }
+    }
+
+    if misplaced.is_empty() || !confirm("\nMove them to target-specific sections?") {
+        return Ok(());
+    }
+
+    for (name, cfg) in &misplaced {
+        let Some(dependencies) = doc
+            .get_mut("dependencies")
+            .and_then(Item::as_table_like_mut)
+        else {
+            break;
+        };
+        // Keys may use hyphens where the source uses underscores
+        let Some(key) = dependencies
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .find(|key| key.replace('-', "_") == *name)
+        else {
+            continue;
+        };
+        let Some(item) = dependencies.remove(&key) else {
+            continue;
+        };
+
+        let targets = doc
+            .entry("target")
+            .or_insert(Item::Table(implicit_table()))
+            .as_table_like_mut()
+            .ok_or("[target] is not a table")?;
+        let target = targets
+            .entry(cfg)
+            .or_insert(Item::Table(implicit_table()))
+            .as_table_like_mut()
+            .ok_or("target section is not a table")?;
+        target
+            .entry("dependencies")
+            .or_insert(Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or("target dependencies is not a table")?
+            .insert(&key, item);
+        println!("✓ Moved {} to [target.'{}'.dependencies]", key, cfg);
+    }
+    manifest::write_manifest(&doc)?;
+
+    Ok(())
+}
+
+/// A table that only appears as part of dotted headers like `[target.x.dependencies]`.
+fn implicit_table() -> Table {
+    let mut table = Table::new();
+    table.set_implicit(true);
+    table
+}
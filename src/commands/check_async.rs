@@ -0,0 +1,91 @@
+use crate::{manifest, source};
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Async runtimes that each bring their own executor and reactor.
+const RUNTIMES: &[&str] = &["tokio", "async-std", "smol"];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let dependencies = manifest::direct_dependencies(&doc);
+    let runtimes: Vec<&str> = RUNTIMES
+        .iter()
+        .copied()
+        .filter(|runtime| dependencies.iter().any(|d| d == runtime))
+        .collect();
+
+    let mut warnings = 0;
+    match runtimes.as_slice() {
+        [] => println!("No async runtime found in Cargo.toml."),
+        [runtime] => println!("✓ {} is the only async runtime", runtime),
+        _ => {
+            warnings += 1;
+            println!(
+                "✗ Multiple async runtimes are dependencies: {}",
+                runtimes.join(", ")
+            );
+            println!(
+                "    Futures that rely on one runtime's reactor (timers, sockets) panic or hang \
+                 when polled by another; pick one and drop the rest."
+            );
+        }
+    }
+
+    if !runtimes.is_empty() && dependencies.iter().any(|d| d == "async-trait") {
+        warnings += 1;
+        let uses = async_trait_uses()?;
+        println!(
+            "✗ async-trait is a dependency ({} #[async_trait] attribute{}); \
+             `async fn` in traits is stable since Rust 1.75",
+            uses,
+            if uses == 1 { "" } else { "s" }
+        );
+        println!(
+            "    Traits that are never used as `dyn Trait` can drop the attribute and the boxed futures."
+        );
+        if let Some(version) = rust_version(&doc)
+            && below_1_75(&version)
+        {
+            println!(
+                "    rust-version is {}; it needs to be at least 1.75 first.",
+                version
+            );
+        }
+    }
+
+    if warnings == 0 && !runtimes.is_empty() {
+        println!("✓ Async dependencies are consistent.");
+    }
+    Ok(())
+}
+
+/// Number of `#[async_trait]` attributes under src/.
+fn async_trait_uses() -> Result<usize, Box<dyn std::error::Error>> {
+    let mut count = 0;
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        count += content
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("#[async_trait"))
+            .count();
+    }
+    Ok(count)
+}
+
+fn rust_version(doc: &DocumentMut) -> Option<String> {
+    doc.get("package")
+        .and_then(|package| package.get("rust-version"))
+        .and_then(Item::as_str)
+        .map(str::to_string)
+}
+
+fn below_1_75(version: &str) -> bool {
+    let mut parts = version
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) < (1, 75)
+}
@@ -0,0 +1,44 @@
+use crate::commands::{backticked, check_target};
+use crate::metadata;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let binaries = metadata::target_names(&metadata, "bin");
+
+    if binaries.is_empty() {
+        println!("No binary targets found.");
+        return Ok(());
+    }
+
+    println!("Checking {} binary target(s)...\n", binaries.len());
+
+    let mut failing = 0;
+    for binary in &binaries {
+        let Some(missing) = check_target("--bin", binary)? else {
+            println!("✓ Binary `{}` compiles", binary);
+            continue;
+        };
+
+        failing += 1;
+        if missing.is_empty() {
+            println!(
+                "✗ Binary `{}` fails to compile (no missing crates detected)",
+                binary
+            );
+        } else {
+            println!("✗ Binary `{}` is missing: {}", binary, backticked(&missing));
+        }
+    }
+
+    if failing > 0 {
+        println!(
+            "\n{} of {} binaries failed to compile.",
+            failing,
+            binaries.len()
+        );
+    } else {
+        println!("\nAll binaries compile.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::commands::{check_features, feature_combinations};
+use crate::manifest;
+
+pub fn run(max_feature_combinations: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let features = manifest::feature_names(&doc);
+
+    if features.is_empty() {
+        println!("No features declared in [features].");
+        return Ok(());
+    }
+
+    let total = 1usize
+        .checked_shl(features.len() as u32)
+        .unwrap_or(usize::MAX);
+    println!(
+        "Checking up to {} of {} feature combinations ({} features)...\n",
+        max_feature_combinations.min(total),
+        total,
+        features.len()
+    );
+
+    let combinations = feature_combinations(&features, Some(max_feature_combinations));
+    let checked = combinations.len();
+    let mut failures = Vec::new();
+
+    for combination in &combinations {
+        let names: Vec<&str> = combination.iter().map(String::as_str).collect();
+        let label = if names.is_empty() {
+            "(no features)".to_string()
+        } else {
+            names.join(",")
+        };
+
+        match check_features(&names)? {
+            None => println!("✓ {}", label),
+            Some(error) => {
+                println!("✗ {}", label);
+                failures.push((label, error));
+            }
+        }
+    }
+
+    if checked < total {
+        println!(
+            "\nStopped after {} combinations; raise --max-feature-combinations to check more.",
+            checked
+        );
+    }
+
+    if failures.is_empty() {
+        println!("\n✓ All {} checked feature combinations compile.", checked);
+        return Ok(());
+    }
+
+    println!("\nFeature combinations that fail to compile:");
+    for (label, error) in &failures {
+        println!("  - {}: {}", label, error);
+    }
+
+    Err(format!(
+        "{} of {} feature combinations failed to compile",
+        failures.len(),
+        checked
+    )
+    .into())
+}
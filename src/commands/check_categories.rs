@@ -0,0 +1,250 @@
+use crate::manifest;
+use toml_edit::{DocumentMut, Item};
+
+/// crates.io rejects more than this many categories.
+const MAX_CATEGORIES: usize = 5;
+
+/// Slugs from https://crates.io/category_slugs, last synced October 2026.
+const CATEGORY_SLUGS: &[&str] = &[
+    "accessibility",
+    "aerospace",
+    "aerospace::drones",
+    "aerospace::protocols",
+    "aerospace::simulation",
+    "aerospace::space-protocols",
+    "aerospace::unmanned-aerial-vehicles",
+    "algorithms",
+    "api-bindings",
+    "asynchronous",
+    "authentication",
+    "caching",
+    "command-line-interface",
+    "command-line-utilities",
+    "compilers",
+    "compression",
+    "computer-vision",
+    "concurrency",
+    "config",
+    "cryptography",
+    "cryptography::cryptocurrencies",
+    "data-structures",
+    "database",
+    "database-implementations",
+    "date-and-time",
+    "development-tools",
+    "development-tools::build-utils",
+    "development-tools::cargo-plugins",
+    "development-tools::debugging",
+    "development-tools::ffi",
+    "development-tools::procedural-macro-helpers",
+    "development-tools::profiling",
+    "development-tools::testing",
+    "email",
+    "embedded",
+    "emulators",
+    "encoding",
+    "external-ffi-bindings",
+    "filesystem",
+    "finance",
+    "game-development",
+    "game-engines",
+    "games",
+    "graphics",
+    "gui",
+    "hardware-support",
+    "internationalization",
+    "localization",
+    "mathematics",
+    "memory-management",
+    "multimedia",
+    "multimedia::audio",
+    "multimedia::encoding",
+    "multimedia::images",
+    "multimedia::video",
+    "network-programming",
+    "no-std",
+    "no-std::no-alloc",
+    "os",
+    "os::android-apis",
+    "os::freebsd-apis",
+    "os::linux-apis",
+    "os::macos-apis",
+    "os::unix-apis",
+    "os::windows-apis",
+    "parser-implementations",
+    "parsing",
+    "rendering",
+    "rendering::data-formats",
+    "rendering::engine",
+    "rendering::graphics-api",
+    "rust-patterns",
+    "science",
+    "science::bioinformatics",
+    "science::bioinformatics::genomics",
+    "science::bioinformatics::proteomics",
+    "science::bioinformatics::sequence-analysis",
+    "science::geo",
+    "science::neuroscience",
+    "science::robotics",
+    "security",
+    "simulation",
+    "template-engine",
+    "text-editors",
+    "text-processing",
+    "value-formatting",
+    "virtualization",
+    "visualization",
+    "wasm",
+    "web-programming",
+    "web-programming::http-client",
+    "web-programming::http-server",
+    "web-programming::websocket",
+];
+
+/// Dependencies that hint at what a crate does: (dependency, category).
+const DEPENDENCY_CATEGORIES: &[(&str, &str)] = &[
+    ("clap", "command-line-utilities"),
+    ("structopt", "command-line-utilities"),
+    ("argh", "command-line-utilities"),
+    ("tokio", "asynchronous"),
+    ("async-std", "asynchronous"),
+    ("smol", "asynchronous"),
+    ("futures", "asynchronous"),
+    ("reqwest", "web-programming::http-client"),
+    ("ureq", "web-programming::http-client"),
+    ("axum", "web-programming::http-server"),
+    ("actix-web", "web-programming::http-server"),
+    ("rocket", "web-programming::http-server"),
+    ("warp", "web-programming::http-server"),
+    ("tungstenite", "web-programming::websocket"),
+    ("tokio-tungstenite", "web-programming::websocket"),
+    ("diesel", "database"),
+    ("sqlx", "database"),
+    ("rusqlite", "database"),
+    ("nom", "parsing"),
+    ("pest", "parsing"),
+    ("winnow", "parsing"),
+    ("serde_json", "encoding"),
+    ("chrono", "date-and-time"),
+    ("time", "date-and-time"),
+    ("rayon", "concurrency"),
+    ("crossbeam", "concurrency"),
+    ("image", "multimedia::images"),
+    ("bevy", "game-development"),
+    ("wasm-bindgen", "wasm"),
+    ("embedded-hal", "embedded"),
+    ("cortex-m", "embedded"),
+    ("syn", "development-tools::procedural-macro-helpers"),
+    ("quote", "development-tools::procedural-macro-helpers"),
+    ("rustls", "cryptography"),
+    ("ring", "cryptography"),
+    ("sha2", "cryptography"),
+    ("flate2", "compression"),
+    ("zstd", "compression"),
+    ("egui", "gui"),
+    ("iced", "gui"),
+    ("cargo_metadata", "development-tools::cargo-plugins"),
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let package = doc
+        .get("package")
+        .and_then(Item::as_table_like)
+        .ok_or("Cargo.toml has no [package] section")?;
+
+    let categories: Vec<String> = package
+        .get("categories")
+        .and_then(Item::as_array)
+        .map(|categories| {
+            categories
+                .iter()
+                .filter_map(|c| c.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut invalid = 0;
+    if categories.is_empty() {
+        println!("✗ [package] has no categories; the crate won't show up when browsing crates.io");
+    }
+    for category in &categories {
+        if CATEGORY_SLUGS.contains(&category.as_str()) {
+            println!("✓ {}", category);
+        } else {
+            invalid += 1;
+            match closest_slug(category) {
+                Some(slug) => println!(
+                    "✗ {} is not a crates.io category (did you mean {}?)",
+                    category, slug
+                ),
+                None => println!("✗ {} is not a crates.io category", category),
+            }
+        }
+    }
+    if categories.len() > MAX_CATEGORIES {
+        invalid += 1;
+        println!(
+            "✗ {} categories listed; crates.io accepts at most {}",
+            categories.len(),
+            MAX_CATEGORIES
+        );
+    }
+
+    let suggestions = suggested_categories(&doc, &categories);
+    if !suggestions.is_empty() {
+        println!("\nSuggested categories:");
+        for (category, reason) in &suggestions {
+            println!("  - {} ({})", category, reason);
+        }
+    }
+
+    if invalid == 0 && !categories.is_empty() {
+        println!("\n✓ All categories are valid crates.io slugs.");
+    } else if invalid > 0 {
+        println!(
+            "\nInvalid categories are dropped when publishing; see https://crates.io/category_slugs"
+        );
+    }
+    Ok(())
+}
+
+/// A valid slug that differs from `category` only in case or separators, or
+/// whose last segment matches it (`http-client` -> `web-programming::http-client`).
+fn closest_slug(category: &str) -> Option<&'static str> {
+    let normalized = category.to_lowercase().replace(['_', ' '], "-");
+    CATEGORY_SLUGS
+        .iter()
+        .copied()
+        .find(|slug| *slug == normalized || slug.rsplit("::").next() == Some(normalized.as_str()))
+}
+
+/// Categories implied by the package name and dependencies that aren't
+/// listed yet, with the reason for each one.
+fn suggested_categories(doc: &DocumentMut, listed: &[String]) -> Vec<(&'static str, String)> {
+    let dependencies = manifest::direct_dependencies(doc);
+    let mut suggestions: Vec<(&'static str, String)> = Vec::new();
+
+    let name = doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .unwrap_or_default();
+    if name.starts_with("cargo-") {
+        suggestions.push((
+            "development-tools::cargo-plugins",
+            format!("named {}", name),
+        ));
+    }
+
+    for (dependency, category) in DEPENDENCY_CATEGORIES {
+        if dependencies.iter().any(|d| d == dependency)
+            && !suggestions.iter().any(|(c, _)| c == category)
+        {
+            suggestions.push((category, format!("uses {}", dependency)));
+        }
+    }
+
+    suggestions.retain(|(category, _)| !listed.iter().any(|l| l == category));
+    suggestions
+}
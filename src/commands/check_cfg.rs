@@ -0,0 +1,90 @@
+use crate::{metadata, source};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let declared: BTreeSet<String> = metadata::packages(&metadata)
+        .iter()
+        .filter_map(|package| package["features"].as_object())
+        .flat_map(|features| features.keys().cloned())
+        .collect();
+
+    println!("Running cargo check with cfg checking...\n");
+    let output = Command::new("cargo")
+        .args(["check", "--all-targets"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let unexpected_regex = Regex::new(
+        r"warning: unexpected `cfg` condition (name|value): `([^`]+)`\s*\n\s*--> (\S+)",
+    )?;
+    let mut unexpected = 0;
+    for cap in unexpected_regex.captures_iter(&stderr) {
+        unexpected += 1;
+        let (kind, flag, location) = (&cap[1], &cap[2], &cap[3]);
+        let hint = match closest(flag, &declared) {
+            Some(feature) if kind == "value" => format!(" (did you mean feature `{}`?)", feature),
+            _ => String::new(),
+        };
+        println!("✗ {}: unknown cfg {} `{}`{}", location, kind, flag, hint);
+    }
+    if unexpected == 0 {
+        println!("✓ No unknown cfg flags reported by the compiler.");
+    }
+
+    // Features that no #[cfg]/cfg! in the source refers to
+    let feature_regex = Regex::new(r#"feature\s*=\s*"([^"]+)""#)?;
+    let mut referenced = BTreeSet::new();
+    for file in source::rust_files(Path::new(".")) {
+        let content = fs::read_to_string(&file)?;
+        for line in content.lines().filter(|line| line.contains("cfg")) {
+            for cap in feature_regex.captures_iter(line) {
+                referenced.insert(cap[1].to_string());
+            }
+        }
+    }
+
+    let unreferenced: Vec<&String> = declared
+        .iter()
+        .filter(|feature| *feature != "default" && !referenced.contains(*feature))
+        .collect();
+    if !unreferenced.is_empty() {
+        println!("\nFeatures declared in [features] but never used in a cfg:");
+        for feature in unreferenced {
+            println!("  - {}", feature);
+        }
+        println!("This is fine for features that only enable dependency features.");
+    }
+
+    Ok(())
+}
+
+/// The declared feature closest to `name`, if it's plausibly a typo.
+fn closest<'a>(name: &str, candidates: &'a BTreeSet<String>) -> Option<&'a String> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
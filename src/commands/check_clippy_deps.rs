@@ -0,0 +1,137 @@
+use crate::commands::confirm;
+use crate::{cratesio, install_crates, is_std_module, manifest, source};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// A crate that clippy mentioned while explaining a lint.
+struct Suggestion {
+    lint: String,
+    message: String,
+    locations: Vec<String>,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Running cargo clippy...\n");
+
+    let output = Command::new("cargo")
+        .args(["clippy", "--all-targets"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let doc = manifest::read_manifest()?;
+    let declared: Vec<String> = manifest::direct_dependencies(&doc)
+        .iter()
+        .map(|name| name.replace('-', "_"))
+        .collect();
+    let own_name = manifest::own_crate_name(&doc);
+    let mut sources = Vec::new();
+    for file in source::rust_files(Path::new(".")) {
+        sources.push(fs::read_to_string(&file)?);
+    }
+    // Clippy may mention the package's own modules in paths too
+    let local_modules = source::declared_modules(sources.iter().map(String::as_str));
+
+    // Only help lines that name a crate outright count; paths such as
+    // `foo::bar` in other diagnostics usually point at existing code
+    let help_regex = Regex::new(r"^\s*(?:= )?help: ")?;
+    let mention_regex = Regex::new(r"the `([a-z][a-z0-9_-]*)` crate|crate `([a-z][a-z0-9_-]*)`")?;
+    let lint_regex = Regex::new(r"clippy::([a-z_]+)")?;
+    let location_regex = Regex::new(r"--> (\S+)")?;
+
+    let mut mentioned: BTreeMap<String, Suggestion> = BTreeMap::new();
+    for block in diagnostics(&stderr) {
+        let Some(lint) = lint_regex.captures(&block).map(|cap| cap[1].to_string()) else {
+            continue;
+        };
+        let first_line = block.lines().next().unwrap_or_default();
+        let message = first_line
+            .split_once(": ")
+            .map_or(first_line, |(_, message)| message)
+            .to_string();
+        let location = location_regex
+            .captures(&block)
+            .map(|cap| cap[1].to_string())
+            .unwrap_or_default();
+
+        let help_lines = block.lines().filter(|line| help_regex.is_match(line));
+        for cap in help_lines.flat_map(|line| mention_regex.captures_iter(line)) {
+            let name = cap
+                .iter()
+                .skip(1)
+                .flatten()
+                .next()
+                .map(|m| m.as_str().replace('-', "_"))
+                .unwrap_or_default();
+            if is_std_module(&name)
+                || ["crate", "self", "super", "clippy", "rustdoc"].contains(&name.as_str())
+                || name == own_name
+                || local_modules.contains(&name)
+                || declared.contains(&name)
+            {
+                continue;
+            }
+
+            let suggestion = mentioned.entry(name).or_insert(Suggestion {
+                lint: lint.clone(),
+                message: message.clone(),
+                locations: Vec::new(),
+            });
+            if !location.is_empty() && !suggestion.locations.contains(&location) {
+                suggestion.locations.push(location.clone());
+            }
+        }
+    }
+
+    // Only names that are actually published count as crate suggestions.
+    let mut suggestions = Vec::new();
+    for (name, suggestion) in mentioned {
+        if let Some((package, _)) = cratesio::find_crate(&name) {
+            suggestions.push((package, suggestion));
+        }
+    }
+
+    if suggestions.is_empty() {
+        println!("✓ No dependency suggestions from clippy.");
+        return Ok(());
+    }
+
+    println!("Dependency suggestions from clippy:");
+    for (package, suggestion) in &suggestions {
+        println!(
+            "  - {} via clippy::{}: {}",
+            package, suggestion.lint, suggestion.message
+        );
+        for location in &suggestion.locations {
+            println!("      {}", location);
+        }
+    }
+
+    let missing: Vec<String> = suggestions
+        .iter()
+        .map(|(package, _)| package.clone())
+        .collect();
+    println!();
+    if confirm(&format!("Add {}?", missing.join(", "))) {
+        install_crates(&missing);
+    }
+
+    Ok(())
+}
+
+/// Splits cargo's human-readable output into one string per diagnostic.
+fn diagnostics(output: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with("warning") || line.starts_with("error") {
+            blocks.push(String::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    blocks
+}
@@ -0,0 +1,144 @@
+use crate::config::{self, CONFIG_PATH};
+use crate::manifest;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+/// Builds go to a separate target directory so they start from scratch
+/// without wiping the project's regular build cache.
+const TIMINGS_TARGET_DIR: &str = "target/cargo-tidy-timings";
+
+struct BuildTimings {
+    total_seconds: f64,
+    /// Seconds spent compiling each crate, keyed by `name version`
+    units: BTreeMap<String, f64>,
+}
+
+pub fn run(add: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = config::read_config()?;
+    let max_seconds = config
+        .get("compile-budget")
+        .and_then(|budget| budget.get("max_seconds"))
+        .and_then(|seconds| {
+            seconds
+                .as_float()
+                .or_else(|| seconds.as_integer().map(|s| s as f64))
+        });
+
+    if max_seconds.is_none() && add.is_none() {
+        return Err(format!(
+            "no budget configured; add `[compile-budget] max_seconds = <n>` to {}",
+            CONFIG_PATH
+        )
+        .into());
+    }
+
+    println!("Timing a clean build...");
+    let mut timings = timed_clean_build()?;
+    println!("Total compile time: {:.1}s", timings.total_seconds);
+
+    if let Some(crate_name) = &add {
+        println!("\nTiming a clean build with {} added...", crate_name);
+        let with_crate = manifest::with_restored_manifest(|| {
+            let output = Command::new("cargo").args(["add", crate_name]).output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("cargo add {} failed: {}", crate_name, stderr.trim()).into());
+            }
+            timed_clean_build()
+        })?;
+
+        println!(
+            "Total compile time: {:.1}s ({:+.1}s for {} and its dependencies)",
+            with_crate.total_seconds,
+            with_crate.total_seconds - timings.total_seconds,
+            crate_name
+        );
+        timings = with_crate;
+    }
+
+    let mut slowest: Vec<(&String, &f64)> = timings.units.iter().collect();
+    slowest.sort_by(|a, b| b.1.total_cmp(a.1));
+    if !slowest.is_empty() {
+        println!("\nSlowest crates:");
+        for (unit, seconds) in slowest.iter().take(5) {
+            println!("  - {} ({:.1}s)", unit, seconds);
+        }
+    }
+
+    let Some(max_seconds) = max_seconds else {
+        return Ok(());
+    };
+
+    println!();
+    if timings.total_seconds > max_seconds {
+        return Err(format!(
+            "compile time {:.2}s exceeds the budget of {:.2}s",
+            timings.total_seconds, max_seconds
+        )
+        .into());
+    }
+    println!(
+        "✓ Compile time {:.2}s is within the budget of {:.2}s",
+        timings.total_seconds, max_seconds
+    );
+    Ok(())
+}
+
+fn timed_clean_build() -> Result<BuildTimings, Box<dyn std::error::Error>> {
+    let _ = fs::remove_dir_all(TIMINGS_TARGET_DIR);
+
+    let start = Instant::now();
+    let output = Command::new("cargo")
+        .args(["build", "--timings"])
+        .env("CARGO_TARGET_DIR", TIMINGS_TARGET_DIR)
+        .output()?;
+    let total_seconds = start.elapsed().as_secs_f64();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("build failed: {}", stderr.trim()).into());
+    }
+
+    let report = Path::new(TIMINGS_TARGET_DIR)
+        .join("cargo-timings")
+        .join("cargo-timing.html");
+    let units = unit_durations(&fs::read_to_string(report).unwrap_or_default());
+
+    Ok(BuildTimings {
+        total_seconds,
+        units,
+    })
+}
+
+/// Extracts per-crate durations from the `UNIT_DATA` array embedded in the
+/// HTML report. `--timings=json` would be simpler but is nightly-only.
+fn unit_durations(report: &str) -> BTreeMap<String, f64> {
+    let mut units = BTreeMap::new();
+
+    let Some(start) = report.find("const UNIT_DATA = ") else {
+        return units;
+    };
+    let data = &report[start + "const UNIT_DATA = ".len()..];
+    let Some(Ok(Value::Array(entries))) = serde_json::Deserializer::from_str(data)
+        .into_iter::<Value>()
+        .next()
+    else {
+        return units;
+    };
+
+    for entry in entries {
+        if let (Some(name), Some(version), Some(duration)) = (
+            entry["name"].as_str(),
+            entry["version"].as_str(),
+            entry["duration"].as_f64(),
+        ) {
+            *units.entry(format!("{} {}", name, version)).or_insert(0.0) += duration;
+        }
+    }
+
+    units
+}
@@ -0,0 +1,123 @@
+use crate::commands::installed_targets;
+use crate::config::{self, CONFIG_PATH};
+use crate::extract_missing_crates;
+use std::process::Command;
+
+const DEFAULT_TARGETS: &[&str] = &["aarch64-unknown-linux-gnu"];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = config::read_config()?;
+    let targets: Vec<String> = config
+        .get("cross")
+        .and_then(|cross| cross.get("targets"))
+        .and_then(|targets| targets.as_array())
+        .map(|targets| {
+            targets
+                .iter()
+                .filter_map(|target| target.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_else(|| DEFAULT_TARGETS.iter().map(|t| t.to_string()).collect());
+
+    println!("Checking the host build first...");
+    let host = Command::new("cargo").arg("check").output()?;
+    let host_stderr = String::from_utf8_lossy(&host.stderr);
+    let host_errors = error_lines(&host_stderr);
+    let host_missing = extract_missing_crates(&host_stderr);
+
+    let installed = installed_targets();
+    let mut failed = 0;
+    for target in &targets {
+        println!("\nChecking {}...", target);
+        if !installed.contains(target) {
+            println!(
+                "✗ Target {} is not installed; run `rustup target add {}`",
+                target, target
+            );
+            failed += 1;
+            continue;
+        }
+
+        let output = Command::new("cargo")
+            .args(["check", "--target", target])
+            .output()?;
+        if output.status.success() {
+            println!("✓ {} compiles", target);
+            continue;
+        }
+        failed += 1;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let errors: Vec<String> = error_lines(&stderr)
+            .into_iter()
+            .filter(|error| !host_errors.contains(error))
+            .collect();
+        let missing: Vec<String> = extract_missing_crates(&stderr)
+            .into_iter()
+            .filter(|name| !host_missing.contains(name))
+            .collect();
+
+        if errors.is_empty() {
+            println!("✗ {} fails with the same errors as the host build", target);
+            continue;
+        }
+
+        println!("✗ Errors specific to {}:", target);
+        for error in &errors {
+            println!("  - {}", error);
+        }
+
+        if !missing.is_empty() {
+            println!(
+                "\nCrates needed for {} that aren't declared for it:",
+                target
+            );
+            for name in &missing {
+                println!("  - {}", name);
+            }
+            println!("\nDeclare them in a target-specific section, for example:");
+            println!("[target.'{}'.dependencies]", target_cfg(target));
+            for name in &missing {
+                println!("{} = \"*\"", name);
+            }
+        }
+    }
+
+    if targets == DEFAULT_TARGETS && config.get("cross").is_none() {
+        println!(
+            "\nConfigure other targets with `[cross] targets = [...]` in {}.",
+            CONFIG_PATH
+        );
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} cross targets failed", failed, targets.len()).into());
+    }
+    Ok(())
+}
+
+/// The `error...` headline of each diagnostic together with its location, so
+/// the same error on the host and the cross target compares equal.
+fn error_lines(stderr: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut lines = stderr.lines().peekable();
+    while let Some(line) = lines.next() {
+        if !line.starts_with("error") || line.starts_with("error: could not compile") {
+            continue;
+        }
+        match lines
+            .peek()
+            .and_then(|next| next.trim().strip_prefix("--> "))
+        {
+            Some(location) => errors.push(format!("{} ({})", line, location)),
+            None => errors.push(line.to_string()),
+        }
+    }
+    errors
+}
+
+/// A cfg expression matching the architecture of a target triple.
+fn target_cfg(target: &str) -> String {
+    let arch = target.split('-').next().unwrap_or(target);
+    format!("cfg(target_arch = \"{}\")", arch)
+}
@@ -0,0 +1,139 @@
+use crate::{manifest, metadata};
+use serde_json::Value;
+use toml_edit::Item;
+
+/// A crate whose default features are commonly turned off.
+struct RiskyDefault {
+    krate: &'static str,
+    /// What the defaults pull in and why that matters
+    implication: &'static str,
+    /// Features to enable explicitly after `default-features = false`
+    recommended: &'static str,
+}
+
+const RISKY_DEFAULTS: &[RiskyDefault] = &[
+    RiskyDefault {
+        krate: "reqwest",
+        implication: "link the system TLS library (OpenSSL on Linux) through native-tls, \
+                      plus HTTP/2 and charset detection",
+        recommended: "rustls-tls, and only the protocols you use",
+    },
+    RiskyDefault {
+        krate: "diesel",
+        implication: "enable the `with-deprecated` APIs and 32-column tables, which mostly cost \
+                      compile time",
+        recommended: "your backend (postgres, mysql or sqlite) and the column size you need",
+    },
+    RiskyDefault {
+        krate: "tokio-tungstenite",
+        implication: "enable the `connect` helper with its TLS stack",
+        recommended: "rustls-tls-webpki-roots, or nothing for server-only use",
+    },
+    RiskyDefault {
+        krate: "git2",
+        implication: "build libgit2 with HTTPS and SSH transports, linking OpenSSL and libssh2",
+        recommended: "https or ssh only if you clone over them",
+    },
+    RiskyDefault {
+        krate: "ureq",
+        implication: "enable gzip and the rustls TLS backend with bundled webpki roots",
+        recommended: "the TLS backend and roots your deployment trusts",
+    },
+    RiskyDefault {
+        krate: "sqlx",
+        implication: "enable the query macros (which connect to a database at compile time), \
+                      migrations and the `any` driver",
+        recommended: "runtime and database features you use",
+    },
+    RiskyDefault {
+        krate: "chrono",
+        implication: "pull in the `clock` feature with the OS time zone lookup, and `oldtime` \
+                      in older versions (RUSTSEC-2020-0071)",
+        recommended: "std and clock only if you need the local time zone",
+    },
+    RiskyDefault {
+        krate: "rand",
+        implication: "enable std, OS entropy and the thread-local generator",
+        recommended: "std_rng or small_rng as needed",
+    },
+    RiskyDefault {
+        krate: "image",
+        implication: "build every image format decoder, each one extra parser attack surface",
+        recommended: "only the formats you decode (png, jpeg, ...)",
+    },
+    RiskyDefault {
+        krate: "zip",
+        implication: "enable every compression method, including bzip2 and zstd C libraries",
+        recommended: "deflate and the methods you read",
+    },
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    // Resolved packages know their exact default feature lists
+    let metadata = metadata::cargo_metadata(&[]).ok();
+
+    let mut warnings = 0;
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            let package = manifest::package_name(key, item);
+            let Some(risky) = RISKY_DEFAULTS.iter().find(|r| r.krate == package) else {
+                continue;
+            };
+            if !uses_default_features(item) {
+                println!("✓ {} in [{}] has default-features = false", key, section);
+                continue;
+            }
+
+            warnings += 1;
+            println!("✗ {} in [{}] uses its default features", key, section);
+            if let Some(defaults) = metadata
+                .as_ref()
+                .and_then(|metadata| default_features(metadata, &package))
+            {
+                println!("    default = [{}]", defaults.join(", "));
+            }
+            println!("    The defaults {}.", risky.implication);
+            println!(
+                "    Consider `default-features = false` with: {}",
+                risky.recommended
+            );
+        }
+    }
+
+    if warnings == 0 {
+        println!("✓ No dependencies pull in default features that are commonly disabled.");
+    } else {
+        println!(
+            "\n{} dependencies use default features worth reviewing.",
+            warnings
+        );
+    }
+    Ok(())
+}
+
+/// Plain version strings and tables without `default-features = false`
+/// both keep the defaults. Workspace-inherited specs are judged as written.
+fn uses_default_features(item: &Item) -> bool {
+    item.as_table_like()
+        .and_then(|spec| {
+            spec.get("default-features")
+                .or_else(|| spec.get("default_features"))
+        })
+        .and_then(Item::as_bool)
+        .unwrap_or(true)
+}
+
+/// The `default` feature list of a resolved package.
+fn default_features(metadata: &Value, package: &str) -> Option<Vec<String>> {
+    metadata::packages(metadata)
+        .into_iter()
+        .find(|p| p["name"] == package)?["features"]["default"]
+        .as_array()
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|f| f.as_str().map(str::to_string))
+                .collect()
+        })
+}
@@ -0,0 +1,194 @@
+use crate::commands::{backticked, check_dep_graph};
+use crate::metadata;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Resolving with default features...");
+    let default = resolve(&[])?;
+    println!("Resolving with --all-features...");
+    let all = resolve(&["--all-features"])?;
+
+    let packages = metadata::packages(&all);
+    let by_id: HashMap<&str, &Value> = packages
+        .iter()
+        .filter_map(|package| Some((package["id"].as_str()?, package)))
+        .collect();
+    let label = |id: &str| -> String {
+        by_id
+            .get(id)
+            .map(|package| {
+                format!(
+                    "{} {}",
+                    package["name"].as_str().unwrap_or("?"),
+                    package["version"].as_str().unwrap_or("?")
+                )
+            })
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let default_edges = check_dep_graph::dependency_edges(&default);
+    let all_edges = check_dep_graph::dependency_edges(&all);
+    let is_gated = |from: &str, to: &str| {
+        !default_edges
+            .get(from)
+            .is_some_and(|deps| deps.contains(&to))
+    };
+
+    // Edges that only exist once features are on, with the features behind them
+    let mut gated = 0;
+    for (from, deps) in &all_edges {
+        for to in deps.iter().filter(|to| is_gated(from, to)) {
+            if gated == 0 {
+                println!("\nDependencies pulled in only by non-default features:");
+            }
+            gated += 1;
+            println!(
+                "  - {} -> {}{}",
+                label(from),
+                label(to),
+                feature_note(&by_id, from, to)
+            );
+        }
+    }
+    if gated == 0 {
+        println!("\n✓ --all-features adds no dependency edges; nothing to check.");
+        return Ok(());
+    }
+
+    // Cargo accepted --all-features, so any cycle left runs through a gated edge
+    let mut cycles = 0;
+    for cycle in check_dep_graph::find_cycles(&all_edges) {
+        if !cycle.windows(2).any(|pair| is_gated(pair[0], pair[1])) {
+            continue;
+        }
+        cycles += 1;
+        println!("\n✗ Feature-induced cycle:");
+        println!("    {}", label(cycle[0]));
+        for pair in cycle.windows(2) {
+            println!(
+                "    -> {}{}",
+                label(pair[1]),
+                feature_note(&by_id, pair[0], pair[1])
+            );
+        }
+    }
+
+    // A gated edge X -> Y is a latent cycle when Y has an optional dependency
+    // back on X: turning on that feature of Y would close the loop
+    let mut latent: BTreeSet<(String, String)> = BTreeSet::new();
+    for (from, deps) in &all_edges {
+        for to in deps.iter().filter(|to| is_gated(from, to)) {
+            let (Some(source), Some(target)) = (by_id.get(from), by_id.get(to)) else {
+                continue;
+            };
+            let Some(source_name) = source["name"].as_str() else {
+                continue;
+            };
+            let back_features = enabling_features(target, source_name, true);
+            if back_features.is_empty() {
+                continue;
+            }
+            if latent.insert((label(from), label(to))) {
+                println!("\n✗ Suspected feature cycle:");
+                println!(
+                    "    {} -> {}{}",
+                    label(from),
+                    label(to),
+                    feature_note(&by_id, from, to)
+                );
+                println!(
+                    "    {} -> {} (via feature {})",
+                    label(to),
+                    source_name,
+                    backticked(&back_features)
+                );
+            }
+        }
+    }
+
+    if cycles == 0 && latent.is_empty() {
+        println!(
+            "\n✓ No feature-induced cycles among {} gated edge(s).",
+            gated
+        );
+        Ok(())
+    } else {
+        println!(
+            "\nMove the shared code into a crate both sides can depend on, or make sure the features are never enabled together."
+        );
+        Err(format!(
+            "{} feature-induced cycle(s) and {} suspected cycle(s)",
+            cycles,
+            latent.len()
+        )
+        .into())
+    }
+}
+
+/// `cargo metadata`, reporting a cycle cargo refused to resolve. The lockfile
+/// covers every optional dependency, so this can fail even without features.
+fn resolve(extra_args: &[&str]) -> Result<Value, Box<dyn std::error::Error>> {
+    match metadata::cargo_metadata(extra_args) {
+        Err(e) if e.to_string().contains("cyclic package dependency") => {
+            println!("\n✗ Cargo rejected a dependency cycle:");
+            println!("    {}", e);
+            Err("the dependency graph contains a cycle".into())
+        }
+        result => result,
+    }
+}
+
+/// ` (via feature `a`, `b`)` when `from` enables `to` through its features,
+/// or an empty string for unconditional dependencies.
+fn feature_note(by_id: &HashMap<&str, &Value>, from: &str, to: &str) -> String {
+    let (Some(source), Some(target)) = (by_id.get(from), by_id.get(to)) else {
+        return String::new();
+    };
+    let features = enabling_features(source, target["name"].as_str().unwrap_or_default(), false);
+    if features.is_empty() {
+        String::new()
+    } else {
+        format!(" (via feature {})", backticked(&features))
+    }
+}
+
+/// Features of `package` that enable its dependency on `dep_name`. With
+/// `optional_only`, non-optional dependencies are ignored.
+fn enabling_features(package: &Value, dep_name: &str, optional_only: bool) -> Vec<String> {
+    let Some(dependency) = package["dependencies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|dep| dep["name"] == dep_name && dep["kind"] != "dev")
+    else {
+        return Vec::new();
+    };
+    if optional_only && dependency["optional"] != true {
+        return Vec::new();
+    }
+    // Features refer to the dependency by its key, which a rename changes
+    let key = dependency["rename"].as_str().unwrap_or(dep_name);
+    let enables = |entry: &str| {
+        entry == key
+            || entry.strip_prefix("dep:") == Some(key)
+            || entry
+                .split_once('/')
+                .is_some_and(|(dep, _)| dep.trim_end_matches('?') == key)
+    };
+
+    package["features"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, entries)| {
+            entries
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .any(enables)
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
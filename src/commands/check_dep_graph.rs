@@ -0,0 +1,196 @@
+use crate::metadata;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    /// Not visited yet
+    White,
+    /// On the current DFS path
+    Gray,
+    /// Fully explored
+    Black,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&[])?;
+    let packages = metadata::packages(&metadata);
+    let labels: HashMap<&str, String> = packages
+        .iter()
+        .filter_map(|package| {
+            Some((
+                package["id"].as_str()?,
+                format!(
+                    "{} {}",
+                    package["name"].as_str()?,
+                    package["version"].as_str()?
+                ),
+            ))
+        })
+        .collect();
+
+    let edges = dependency_edges(&metadata);
+
+    println!("Checking {} packages for cycles...", edges.len());
+    let cycles = find_cycles(&edges);
+    if cycles.is_empty() {
+        println!("✓ The dependency graph is acyclic.");
+    } else {
+        println!("✗ Dependency cycles:");
+        for cycle in &cycles {
+            let path: Vec<&str> = cycle
+                .iter()
+                .map(|id| labels.get(id).map(String::as_str).unwrap_or(id))
+                .collect();
+            println!("  - {}", path.join(" -> "));
+        }
+    }
+
+    println!();
+    let diamonds = incompatible_diamonds(&packages, &edges);
+    if diamonds.is_empty() {
+        println!("✓ No crate is required with incompatible version ranges.");
+    } else {
+        println!("✗ Crates required with incompatible version ranges:");
+        for (name, requirements) in &diamonds {
+            println!("  - {}:", name);
+            for requirement in requirements {
+                println!("      {}", requirement);
+            }
+        }
+    }
+
+    if !cycles.is_empty() {
+        return Err(format!("{} dependency cycle(s) found", cycles.len()).into());
+    }
+    Ok(())
+}
+
+/// Resolved dependency edges by package id. Dev-dependency edges are left
+/// out: cargo allows cycles through them.
+pub fn dependency_edges(metadata: &Value) -> BTreeMap<&str, Vec<&str>> {
+    let mut edges: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for node in metadata["resolve"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let Some(id) = node["id"].as_str() else {
+            continue;
+        };
+        let deps = edges.entry(id).or_default();
+        for dep in node["deps"].as_array().into_iter().flatten() {
+            let is_dev_only = dep["dep_kinds"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().all(|k| k["kind"] == "dev"));
+            if let Some(pkg) = dep["pkg"].as_str()
+                && !is_dev_only
+            {
+                deps.push(pkg);
+            }
+        }
+    }
+    edges
+}
+
+/// DFS with white/gray/black coloring. Reaching a gray node closes a cycle,
+/// which is reported as the path from that node back to itself.
+pub fn find_cycles<'a>(edges: &BTreeMap<&'a str, Vec<&'a str>>) -> Vec<Vec<&'a str>> {
+    let mut colors: HashMap<&str, Color> = edges.keys().map(|id| (*id, Color::White)).collect();
+    let mut cycles = Vec::new();
+
+    for root in edges.keys() {
+        if colors[root] == Color::White {
+            let mut path = Vec::new();
+            visit(root, edges, &mut colors, &mut path, &mut cycles);
+        }
+    }
+    cycles
+}
+
+fn visit<'a>(
+    id: &'a str,
+    edges: &BTreeMap<&'a str, Vec<&'a str>>,
+    colors: &mut HashMap<&'a str, Color>,
+    path: &mut Vec<&'a str>,
+    cycles: &mut Vec<Vec<&'a str>>,
+) {
+    colors.insert(id, Color::Gray);
+    path.push(id);
+
+    for dep in edges.get(id).into_iter().flatten() {
+        match colors.get(dep).copied().unwrap_or(Color::White) {
+            Color::White => visit(dep, edges, colors, path, cycles),
+            Color::Gray => {
+                let start = path.iter().position(|p| p == dep).unwrap_or(0);
+                let mut cycle = path[start..].to_vec();
+                cycle.push(dep);
+                cycles.push(cycle);
+            }
+            Color::Black => {}
+        }
+    }
+
+    path.pop();
+    colors.insert(id, Color::Black);
+}
+
+/// Crates resolved to more than one version, which means the paths leading to
+/// them asked for version ranges that no single version satisfies. Each entry
+/// lists who requires which range.
+fn incompatible_diamonds(
+    packages: &[Value],
+    edges: &BTreeMap<&str, Vec<&str>>,
+) -> BTreeMap<String, Vec<String>> {
+    let by_id: HashMap<&str, &Value> = packages
+        .iter()
+        .filter_map(|package| Some((package["id"].as_str()?, package)))
+        .collect();
+
+    let mut versions: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for package in packages {
+        if let (Some(name), Some(version)) = (package["name"].as_str(), package["version"].as_str())
+        {
+            versions.entry(name).or_default().push(version);
+        }
+    }
+
+    let mut diamonds = BTreeMap::new();
+    for (name, _) in versions.into_iter().filter(|(_, v)| v.len() > 1) {
+        let mut requirements = Vec::new();
+        for (parent, deps) in edges {
+            for dep in deps {
+                let Some(package) = by_id.get(dep) else {
+                    continue;
+                };
+                if package["name"] != name {
+                    continue;
+                }
+                let Some(parent) = by_id.get(parent) else {
+                    continue;
+                };
+                let req = parent["dependencies"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|d| d["name"] == name && d["kind"] != "dev")
+                    .filter_map(|d| d["req"].as_str())
+                    .next()
+                    .unwrap_or("*");
+                requirements.push(format!(
+                    "{} {} requires {} -> {}",
+                    parent["name"].as_str().unwrap_or("?"),
+                    parent["version"].as_str().unwrap_or("?"),
+                    req,
+                    package["version"].as_str().unwrap_or("?")
+                ));
+            }
+        }
+        requirements.sort();
+        requirements.dedup();
+        if !requirements.is_empty() {
+            diamonds.insert(name.to_string(), requirements);
+        }
+    }
+    diamonds
+}
@@ -0,0 +1,77 @@
+use crate::manifest;
+use toml_edit::Item;
+
+const MAX_NAME_LENGTH: usize = 64;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+
+    let mut names: Vec<(String, String)> = Vec::new();
+    if let Some(name) = doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+    {
+        names.push((name.to_string(), "[package] name".to_string()));
+    }
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            names.push((manifest::package_name(key, item), format!("[{}]", section)));
+        }
+    }
+
+    let mut violations = 0;
+    for (name, location) in &names {
+        for rule in naming_violations(name) {
+            violations += 1;
+            println!("✗ `{}` in {}: {}", name, location, rule);
+        }
+    }
+
+    if violations > 0 {
+        return Err(format!("{} naming violation(s) found", violations).into());
+    }
+    println!(
+        "✓ All {} crate names follow crates.io naming rules.",
+        names.len()
+    );
+    Ok(())
+}
+
+/// Every naming rule `name` breaks. crates.io also accepts underscores, so
+/// those aren't reported.
+pub fn naming_violations(name: &str) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if name.is_empty() {
+        violations.push("name is empty".to_string());
+        return violations;
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+    {
+        violations.push(format!(
+            "contains `{}`; only ASCII letters, digits, `-` and `_` are allowed",
+            c
+        ));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        violations.push("must start with an ASCII letter".to_string());
+    }
+    if name.ends_with('-') {
+        violations.push("must not end with a hyphen".to_string());
+    }
+    if name.contains("--") {
+        violations.push("must not contain adjacent hyphens".to_string());
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        violations.push(format!(
+            "is {} characters long; the limit is {}",
+            name.len(),
+            MAX_NAME_LENGTH
+        ));
+    }
+
+    violations
+}
@@ -0,0 +1,95 @@
+use crate::{manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use toml_edit::Item;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let keys = |kind: &str| -> Vec<String> {
+        doc.get(kind)
+            .and_then(Item::as_table_like)
+            .map(|table| table.iter().map(|(key, _)| key.to_string()).collect())
+            .unwrap_or_default()
+    };
+    let normal = keys("dependencies");
+    let dev_only: Vec<String> = keys("dev-dependencies")
+        .into_iter()
+        .filter(|key| !normal.contains(key))
+        .collect();
+
+    if dev_only.is_empty() {
+        println!("No dev-only dependencies to check.");
+        return Ok(());
+    }
+
+    // Dev-dependency key -> regex matching a `use` of it or a qualified path
+    let mut patterns = Vec::new();
+    for key in &dev_only {
+        let name = regex::escape(&key.replace('-', "_"));
+        let pattern = format!(r"^\s*(pub\s+)?use\s+(::)?{}\b|\b{}::", name, name);
+        patterns.push((key, Regex::new(&pattern)?));
+    }
+
+    let mut violations = 0;
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        for (number, line) in non_test_lines(&content) {
+            for (key, regex) in &patterns {
+                if regex.is_match(line) {
+                    violations += 1;
+                    println!(
+                        "✗ {} is listed as a dev-dependency but is used in {}:{} — move to [dependencies] or remove the import.",
+                        key,
+                        file.display(),
+                        number
+                    );
+                }
+            }
+        }
+    }
+
+    if violations == 0 {
+        println!(
+            "✓ None of the {} dev-dependencies are used outside test code in src/.",
+            dev_only.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Lines with their 1-based numbers, leaving out items marked `#[cfg(test)]`
+/// (usually the `mod tests { ... }` block) and comments.
+fn non_test_lines(content: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut skipping = false;
+    let mut depth = 0i32;
+    let mut entered = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if !skipping && trimmed.replace(' ', "").starts_with("#[cfg(test)]") {
+            skipping = true;
+            depth = 0;
+            entered = false;
+            continue;
+        }
+
+        if skipping {
+            depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+            entered |= line.contains('{');
+            // The item ends at its closing brace, or at `;` for one-liners
+            if (entered && depth <= 0) || (!entered && line.contains(';')) {
+                skipping = false;
+            }
+            continue;
+        }
+
+        if !trimmed.starts_with("//") {
+            lines.push((index + 1, line));
+        }
+    }
+
+    lines
+}
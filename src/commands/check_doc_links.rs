@@ -0,0 +1,84 @@
+use crate::{is_std_module, manifest};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Building documentation with broken intra-doc links denied...\n");
+
+    // `cargo rustdoc -- <flags>` refuses packages with several targets, so
+    // the lint goes through RUSTDOCFLAGS and covers the lib and every bin
+    let output = Command::new("cargo")
+        .args(["doc", "--no-deps"])
+        .env("RUSTDOCFLAGS", "--deny rustdoc::broken_intra_doc_links")
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let doc = manifest::read_manifest()?;
+    let dependencies: Vec<String> = manifest::direct_dependencies(&doc)
+        .iter()
+        .map(|name| name.replace('-', "_"))
+        .collect();
+
+    let link_regex = Regex::new(r"unresolved link to `([^`]+)`\s*\n\s*--> (\S+)")?;
+
+    // Links whose first path segment names a crate that isn't a dependency.
+    let mut missing: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut other = Vec::new();
+
+    for cap in link_regex.captures_iter(&stderr) {
+        let link = &cap[1];
+        let location = cap[2].to_string();
+
+        let first = link
+            .trim_start_matches("::")
+            .split("::")
+            .next()
+            .unwrap_or(link);
+        let looks_like_crate = link.contains("::")
+            && first
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            && !is_std_module(first)
+            && !dependencies.iter().any(|dep| dep == first);
+
+        if looks_like_crate {
+            missing
+                .entry(first.to_string())
+                .or_default()
+                .push(format!("{} ({})", location, link));
+        } else {
+            other.push(format!("{}: `{}`", location, link));
+        }
+    }
+
+    if missing.is_empty() && other.is_empty() {
+        if output.status.success() {
+            println!("✓ No broken intra-doc links found.");
+        } else {
+            println!("✗ cargo doc failed for reasons unrelated to doc links:");
+            println!("{}", stderr.trim());
+        }
+        return Ok(());
+    }
+
+    if !missing.is_empty() {
+        println!("Crates referenced by doc links but missing from dependencies:");
+        for (name, locations) in &missing {
+            println!("  - {}", name);
+            for location in locations {
+                println!("      {}", location);
+            }
+        }
+        println!("\nThese only affect documentation builds.");
+    }
+
+    if !other.is_empty() {
+        println!("\nOther broken intra-doc links:");
+        for link in &other {
+            println!("  - {}", link);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::commands::backticked;
+use crate::{extract_missing_crates, metadata};
+use regex::Regex;
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    if metadata::target_names(&metadata, "lib").is_empty() {
+        println!("No library target found; doc tests only run for libraries.");
+        return Ok(());
+    }
+
+    println!("Compiling doc tests...\n");
+    let output = Command::new("cargo").args(["test", "--doc"]).output()?;
+    // Doc test compile errors are printed by the test harness on stdout
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let section_regex = Regex::new(r"(?m)^---- (.+?) stdout ----$")?;
+    let headers: Vec<(usize, String)> = section_regex
+        .captures_iter(&stdout)
+        .filter_map(|cap| Some((cap.get(0)?.start(), cap[1].to_string())))
+        .collect();
+
+    let mut all_missing: Vec<String> = Vec::new();
+    for (index, (start, name)) in headers.iter().enumerate() {
+        let end = headers
+            .get(index + 1)
+            .map(|(next, _)| *next)
+            .unwrap_or(stdout.len());
+        let missing = extract_missing_crates(&stdout[*start..end]);
+        if !missing.is_empty() {
+            println!("✗ {} uses {}", name, backticked(&missing));
+            all_missing.extend(missing);
+        }
+    }
+
+    all_missing.sort();
+    all_missing.dedup();
+    if all_missing.is_empty() {
+        println!("✓ No doc tests are missing crates.");
+        return Ok(());
+    }
+
+    println!("\nDoc tests can only use [dependencies] and [dev-dependencies]. To fix, run:");
+    println!("cargo add --dev {}", all_missing.join(" "));
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use regex::Regex;
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Building documentation with missing docs denied...\n");
+
+    let output = Command::new("cargo")
+        .args(["doc", "--no-deps"])
+        .env("RUSTDOCFLAGS", "-D missing_docs")
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let missing_regex =
+        Regex::new(r"(?:error|warning): missing documentation for (.+)\n\s*--> (\S+)")?;
+    let missing: Vec<(String, String)> = missing_regex
+        .captures_iter(&stderr)
+        .map(|cap| (cap[2].to_string(), cap[1].to_string()))
+        .collect();
+
+    if missing.is_empty() {
+        if output.status.success() {
+            println!("✓ All public items are documented.");
+        } else {
+            println!("✗ cargo doc failed for reasons unrelated to missing docs:");
+            println!("{}", stderr.trim());
+        }
+        return Ok(());
+    }
+
+    for (location, item) in &missing {
+        println!("warning: {}: missing documentation for {}", location, item);
+    }
+    println!("\n{} public item(s) lack documentation.", missing.len());
+    println!("Items re-exported from dependencies with `pub use` are reported at the re-export.");
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::extract_missing_crates;
+use crate::{manifest, metadata};
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::Command;
+use toml_edit::{DocumentMut, Item, value};
+
+const EDITION: &str = "2024";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut doc = manifest::read_manifest()?;
+    let Some(current) = current_edition(&doc)? else {
+        println!(
+            "This virtual manifest sets no [workspace.package] edition; run the check in each member instead."
+        );
+        return Ok(());
+    };
+
+    if current == EDITION {
+        println!("Project already uses edition {}.", EDITION);
+        return Ok(());
+    }
+
+    let Some(toolchain) = edition_toolchain() else {
+        println!(
+            "Edition {} is not supported by the installed stable or nightly toolchain; skipping.",
+            EDITION
+        );
+        return Ok(());
+    };
+
+    println!("Checking project under edition {}...", current);
+    let before = check(toolchain)?;
+
+    // Cargo passes `--edition` to rustc itself, so the edition has to be
+    // switched in the manifest rather than through RUSTFLAGS.
+    let after = manifest::with_restored_manifest(|| {
+        // A package gets its own edition, replacing `edition.workspace = true`
+        // for the check; a virtual manifest switches the shared one
+        let section = if doc.contains_key("package") {
+            doc.get_mut("package")
+        } else {
+            doc.get_mut("workspace")
+                .and_then(|workspace| workspace.get_mut("package"))
+        };
+        if let Some(section) = section.and_then(Item::as_table_like_mut) {
+            section.insert("edition", value(EDITION));
+        }
+        manifest::write_manifest(&doc)?;
+        println!("Checking project under edition {}...\n", EDITION);
+        check(toolchain)
+    })?;
+
+    let new_missing: Vec<&String> = after.missing.difference(&before.missing).collect();
+    let new_errors: Vec<&String> = after.errors.difference(&before.errors).collect();
+
+    if new_missing.is_empty() && new_errors.is_empty() {
+        println!("✓ No new problems under edition {}.", EDITION);
+        return Ok(());
+    }
+
+    if !new_missing.is_empty() {
+        println!("Crates that would be missing under edition {}:", EDITION);
+        for name in new_missing {
+            println!("  - {}", name);
+        }
+    }
+    if !new_errors.is_empty() {
+        println!("New compilation errors under edition {}:", EDITION);
+        for error in new_errors {
+            println!("  - {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+/// The edition in effect for this manifest: the package's own, the one it
+/// inherits with `edition.workspace = true`, or a virtual manifest's
+/// `[workspace.package]` edition. `None` for a virtual manifest without one.
+fn current_edition(doc: &DocumentMut) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let shared = doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("edition"))
+        .and_then(Item::as_str);
+
+    let Some(package) = doc.get("package") else {
+        return Ok(shared.map(str::to_string));
+    };
+    let edition = package.get("edition");
+    if let Some(edition) = edition.and_then(Item::as_str) {
+        return Ok(Some(edition.to_string()));
+    }
+    let inherited = edition
+        .and_then(|edition| edition.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or(false);
+    if !inherited {
+        return Ok(Some("2015".to_string()));
+    }
+    if let Some(shared) = shared {
+        return Ok(Some(shared.to_string()));
+    }
+
+    // Inherited from a workspace root further up; cargo resolves it
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let manifest_path = fs::canonicalize(manifest::MANIFEST_PATH)?;
+    Ok(metadata::workspace_packages(&metadata)
+        .iter()
+        .find(|package| {
+            package["manifest_path"]
+                .as_str()
+                .is_some_and(|path| fs::canonicalize(path).ok() == Some(manifest_path.clone()))
+        })
+        .and_then(|package| package["edition"].as_str())
+        .map(str::to_string))
+}
+
+struct CheckResult {
+    missing: BTreeSet<String>,
+    errors: BTreeSet<String>,
+}
+
+/// Picks a toolchain whose rustc accepts the new edition, preferring the
+/// default one. Returns the `+toolchain` argument to pass to cargo, if any.
+fn edition_toolchain() -> Option<Option<&'static str>> {
+    let supports = |toolchain: Option<&str>| {
+        Command::new("rustc")
+            .args(toolchain)
+            .args(["--edition", EDITION, "--print", "sysroot"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    };
+
+    if supports(None) {
+        Some(None)
+    } else if supports(Some("+nightly")) {
+        Some(Some("+nightly"))
+    } else {
+        None
+    }
+}
+
+fn check(toolchain: Option<&str>) -> Result<CheckResult, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args(toolchain)
+        .args(["check", "--all-targets"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let missing = extract_missing_crates(&stderr).into_iter().collect();
+    let errors = stderr
+        .lines()
+        .filter(|line| line.starts_with("error") && !line.starts_with("error: could not compile"))
+        .map(str::to_string)
+        .collect();
+
+    Ok(CheckResult { missing, errors })
+}
@@ -0,0 +1,193 @@
+use crate::commands::installed_targets;
+use crate::{extract_missing_crates, manifest};
+use regex::Regex;
+use std::process::Command;
+use toml_edit::Item;
+
+/// no_std-compatible replacements for crates that need std: (crate, alternative).
+const NO_STD_ALTERNATIVES: &[(&str, &str)] = &[
+    ("serde_json", "serde-json-core"),
+    ("serde", "serde with `default-features = false`"),
+    ("log", "defmt"),
+    ("env_logger", "defmt-rtt or rtt-target"),
+    ("tokio", "embassy-executor"),
+    ("async-std", "embassy-executor"),
+    ("lazy_static", "lazy_static with the `spin_no_std` feature"),
+    (
+        "once_cell",
+        "once_cell with `default-features = false` and `critical-section`",
+    ),
+    ("crossbeam", "heapless (lock-free queues)"),
+    (
+        "crossbeam-channel",
+        "heapless::spsc or embassy-sync channels",
+    ),
+    ("parking_lot", "critical-section or spin"),
+    (
+        "regex",
+        "regex with `default-features = false` (needs alloc)",
+    ),
+    ("chrono", "chrono with `default-features = false`, or time"),
+    (
+        "rand",
+        "rand with `default-features = false` plus rand_core",
+    ),
+    ("anyhow", "anyhow with `default-features = false`"),
+    ("thiserror", "thiserror 2 with `default-features = false`"),
+    ("byteorder", "byteorder with `default-features = false`"),
+    ("num", "num-traits with `default-features = false`"),
+    ("hex", "hex with `default-features = false`"),
+    ("base64", "base64 with `default-features = false`"),
+    ("bytes", "bytes with `default-features = false`"),
+    ("itertools", "itertools with `default-features = false`"),
+];
+
+pub fn run(target: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !installed_targets().iter().any(|t| t == target) {
+        return Err(format!(
+            "target {} is not installed; run `rustup target add {}`",
+            target, target
+        )
+        .into());
+    }
+
+    println!("Running cargo check --target {}...\n", target);
+    let output = Command::new("cargo")
+        .args(["check", "--keep-going", "--target", target])
+        .output()?;
+    if output.status.success() {
+        println!("✓ The project compiles for {}.", target);
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let doc = manifest::read_manifest()?;
+    let own_name = doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut requires_std = Vec::new();
+    let mut unsupported = Vec::new();
+    let mut other = Vec::new();
+
+    // Each failing crate ends its errors with "could not compile `name`"
+    let failed_regex = Regex::new(r"error: could not compile `([^`]+)`")?;
+    let mut start = 0;
+    for cap in failed_regex.captures_iter(&stderr) {
+        let Some(end) = cap.get(0).map(|m| m.start()) else {
+            continue;
+        };
+        let errors = &stderr[start..end];
+        start = end;
+        let name = cap[1].to_string();
+
+        if errors.contains("can't find crate for `std`") {
+            requires_std.push(name);
+        } else if errors.contains("not supported")
+            || errors.contains("unsupported")
+            || errors.contains("no support")
+        {
+            unsupported.push(name);
+        } else if name != own_name || extract_missing_crates(errors).is_empty() {
+            // The package failing only on missing crates is covered below
+            other.push(name);
+        }
+    }
+    let missing = extract_missing_crates(&stderr);
+
+    if !requires_std.is_empty() {
+        println!("Crates that require std (need a no_std alternative):");
+        for name in &requires_std {
+            if *name == own_name {
+                println!("  - {}: add `#![no_std]` to the crate root", name);
+                continue;
+            }
+            // A transitive crate is usually fixed by changing the direct
+            // dependency that enables its std feature.
+            let direct = pulled_in_by(name, target, &own_name);
+            let via = if direct.is_empty() || direct.contains(name) {
+                String::new()
+            } else {
+                format!(" (pulled in by {})", direct.join(", "))
+            };
+            let alternative = std::iter::once(name)
+                .chain(&direct)
+                .find_map(|krate| NO_STD_ALTERNATIVES.iter().find(|(k, _)| k == krate));
+            match alternative {
+                Some((krate, alternative)) => {
+                    println!(
+                        "  - {}{}: replace {} with {}",
+                        name, via, krate, alternative
+                    )
+                }
+                None => println!("  - {}{}: no known alternative", name, via),
+            }
+        }
+        println!();
+    }
+
+    if !unsupported.is_empty() {
+        println!("Crates without support for {}:", target);
+        for name in &unsupported {
+            println!("  - {}", name);
+        }
+        println!();
+    }
+
+    if !missing.is_empty() {
+        println!("Missing crates:");
+        for name in &missing {
+            println!("  - {}", name);
+        }
+        println!();
+    }
+
+    if !other.is_empty() {
+        println!("Other compilation failures:");
+        for name in &other {
+            println!("  - {}", name);
+        }
+        println!();
+    }
+
+    Err(format!("the project doesn't compile for {}", target).into())
+}
+
+/// Direct dependencies of the package through which `name` is built, found
+/// with an inverted `cargo tree`.
+fn pulled_in_by(name: &str, target: &str, own_name: &str) -> Vec<String> {
+    let Ok(output) = Command::new("cargo")
+        .args(["tree", "-i", name, "--target", target])
+        .args(["--prefix", "depth", "-e", "normal"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // "2serde_json v1.0.0" -> (2, "serde_json")
+    let lines: Vec<(usize, &str)> = stdout
+        .lines()
+        .filter_map(|line| {
+            let digits = line.find(|c: char| !c.is_ascii_digit())?;
+            let depth = line[..digits].parse().ok()?;
+            Some((depth, line[digits..].split(' ').next()?))
+        })
+        .collect();
+
+    let mut direct = Vec::new();
+    for (index, (depth, package)) in lines.iter().enumerate() {
+        if *package != own_name || *depth == 0 {
+            continue;
+        }
+        if let Some((_, parent)) = lines[..index].iter().rev().find(|(d, _)| *d + 1 == *depth)
+            && !direct.iter().any(|d| d == parent)
+        {
+            direct.push(parent.to_string());
+        }
+    }
+    direct
+}
@@ -0,0 +1,163 @@
+use crate::{metadata, source};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Calls that abort instead of propagating an error to `main`'s caller.
+const PANICKING_CALLS: &[&str] = &[".unwrap()", ".expect(", "panic!("];
+
+pub fn run(max_lines: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let main_fn = Regex::new(r"^\s*(pub\s+)?(async\s+)?fn\s+main\s*\(")?;
+
+    let mut checked = 0;
+    let mut suggestions = 0;
+    for package in metadata::workspace_packages(&metadata) {
+        let targets = package["targets"].as_array().cloned().unwrap_or_default();
+        let library = targets
+            .iter()
+            .find(|target| has_kind(target, "lib") || has_kind(target, "rlib"))
+            .and_then(|target| target["name"].as_str())
+            .map(|name| name.replace('-', "_"));
+
+        for target in targets.iter().filter(|target| has_kind(target, "bin")) {
+            let (Some(name), Some(path)) = (target["name"].as_str(), target["src_path"].as_str())
+            else {
+                continue;
+            };
+            let display = relative(path);
+            let Ok(content) = fs::read_to_string(path) else {
+                println!("✗ bin `{}`: could not read {}", name, display);
+                continue;
+            };
+            checked += 1;
+
+            let lines: Vec<&str> = content.lines().collect();
+            let Some(index) = lines.iter().position(|line| main_fn.is_match(line)) else {
+                println!("✗ bin `{}`: no `fn main` found in {}", name, display);
+                suggestions += 1;
+                continue;
+            };
+            let signature: String = lines[index..]
+                .iter()
+                .take_while(|line| !line.contains('{'))
+                .chain(lines[index..].iter().find(|line| line.contains('{')))
+                .copied()
+                .collect();
+            let body: Vec<&str> = source::function_body(&lines[index..])
+                .unwrap_or_default()
+                .into_iter()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("//"))
+                .collect();
+
+            let mut problems = Vec::new();
+            let returns = signature
+                .split_once("->")
+                .map(|(_, ret)| ret.split('{').next().unwrap_or_default().trim());
+            match returns {
+                Some(ret) if ret.contains("Result") || ret.contains("ExitCode") => {}
+                Some(ret) => problems.push(format!(
+                    "main() returns `{}`; return `Result<(), Box<dyn Error>>` or `anyhow::Result<()>`",
+                    ret
+                )),
+                None => problems.push(
+                    "main() returns nothing, so errors can only end it with a panic; \
+                     return `Result<(), Box<dyn Error>>` or `anyhow::Result<()>`"
+                        .to_string(),
+                ),
+            }
+
+            for call in PANICKING_CALLS {
+                let count = body
+                    .iter()
+                    .map(|line| line.matches(call).count())
+                    .sum::<usize>();
+                if count > 0 {
+                    problems.push(format!(
+                        "main() calls `{}` {} time(s); propagate the error with `?` instead",
+                        call.trim_end_matches('('),
+                        count
+                    ));
+                }
+            }
+
+            if body.len() > max_lines {
+                problems.push(format!(
+                    "main() is {} lines long (limit {}); move the logic into library functions",
+                    body.len(),
+                    max_lines
+                ));
+            }
+            match &library {
+                Some(lib) if !uses_library(&content, lib) => problems.push(format!(
+                    "{} never calls into the `{}` library; keep the logic there and call it from main()",
+                    display, lib
+                )),
+                None if body.len() > max_lines => problems.push(format!(
+                    "package {} has no library target; add src/lib.rs so main() can stay thin",
+                    package["name"].as_str().unwrap_or_default()
+                )),
+                _ => {}
+            }
+
+            if problems.is_empty() {
+                println!(
+                    "✓ bin `{}` ({}): thin main() of {} lines",
+                    name,
+                    display,
+                    body.len()
+                );
+            } else {
+                suggestions += problems.len();
+                println!("✗ bin `{}` ({}):", name, display);
+                for problem in &problems {
+                    println!("    {}", problem);
+                }
+            }
+        }
+    }
+
+    if checked == 0 {
+        println!("No binary targets found.");
+    } else if suggestions == 0 {
+        println!("\n✓ All {} binary entry points are thin.", checked);
+    } else {
+        println!(
+            "\n{} suggestion(s) across {} binary entry point(s).",
+            suggestions, checked
+        );
+    }
+    Ok(())
+}
+
+fn has_kind(target: &Value, kind: &str) -> bool {
+    target["kind"]
+        .as_array()
+        .is_some_and(|kinds| kinds.iter().any(|k| k == kind))
+}
+
+/// Whether a binary's source refers to its package's library crate, as
+/// `lib::path` or `use lib`, but not `my_lib::` or `use lib_extra`.
+fn uses_library(content: &str, lib: &str) -> bool {
+    let lib_regex = Regex::new(&format!(
+        r"(?:^|[^\w:])(?:::)?{0}::|\buse\s+(?:::)?{0}\b",
+        regex::escape(lib)
+    ))
+    .unwrap();
+    lib_regex.is_match(content)
+}
+
+/// `src_path` relative to the current directory, for shorter output.
+fn relative(path: &str) -> String {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            Path::new(path)
+                .strip_prefix(cwd)
+                .ok()
+                .map(|p| p.display().to_string())
+        })
+        .unwrap_or_else(|| path.to_string())
+}
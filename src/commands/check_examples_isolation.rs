@@ -0,0 +1,45 @@
+use crate::commands::{backticked, check_target};
+use crate::metadata;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let examples = metadata::target_names(&metadata, "example");
+
+    if examples.is_empty() {
+        println!("No examples found.");
+        return Ok(());
+    }
+
+    println!("Checking {} example(s) individually...\n", examples.len());
+
+    let mut needed: Vec<String> = Vec::new();
+    for example in &examples {
+        let Some(missing) = check_target("--example", example)? else {
+            println!("✓ Example `{}` compiles", example);
+            continue;
+        };
+
+        if missing.is_empty() {
+            println!(
+                "✗ Example `{}` fails to compile (no missing crates detected)",
+                example
+            );
+        } else {
+            println!(
+                "✗ Example `{}` needs dev-dependencies: {}",
+                example,
+                backticked(&missing)
+            );
+            needed.extend(missing);
+        }
+    }
+
+    needed.sort();
+    needed.dedup();
+    if !needed.is_empty() {
+        println!("\nTo fix, run:");
+        println!("cargo add --dev {}", needed.join(" "));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::manifest;
+use toml_edit::{Item, Table};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let Some(features) = doc.get("features").and_then(Item::as_table) else {
+        println!("No [features] section found.");
+        return Ok(());
+    };
+
+    let undocumented = undocumented_features(features);
+    if undocumented.is_empty() {
+        println!("✓ All features in [features] are documented.");
+        return Ok(());
+    }
+
+    println!("Undocumented features:");
+    for feature in &undocumented {
+        println!("  - {}", feature);
+    }
+    println!("\nAdd a `# description` comment on the line above each feature.");
+
+    Err(format!("{} undocumented feature(s)", undocumented.len()).into())
+}
+
+/// Features with no `#` comment directly above them or at the end of their
+/// line. `default` and `_internal` features don't need one.
+fn undocumented_features(features: &Table) -> Vec<String> {
+    features
+        .iter()
+        .filter(|(name, _)| *name != "default" && !name.starts_with('_'))
+        .filter(|(name, item)| {
+            let above = features
+                .key(name)
+                .and_then(|key| key.leaf_decor().prefix())
+                .and_then(|prefix| prefix.as_str())
+                .is_some_and(|prefix| {
+                    prefix
+                        .lines()
+                        .any(|line| line.trim_start().starts_with('#'))
+                });
+            let inline = item
+                .as_value()
+                .and_then(|value| value.decor().suffix())
+                .and_then(|suffix| suffix.as_str())
+                .is_some_and(|suffix| suffix.trim_start().starts_with('#'));
+            !above && !inline
+        })
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
@@ -0,0 +1,73 @@
+use crate::commands::backticked;
+use crate::manifest;
+use toml_edit::Item;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+
+    let mut optional = Vec::new();
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            let is_optional = item
+                .as_table_like()
+                .and_then(|spec| spec.get("optional"))
+                .and_then(Item::as_bool)
+                .unwrap_or(false);
+            if is_optional {
+                optional.push((key.to_string(), section.clone()));
+            }
+        }
+    }
+
+    if optional.is_empty() {
+        println!("No optional dependencies found.");
+        return Ok(());
+    }
+
+    let enabled = manifest::feature_entries(&doc);
+
+    let unreachable: Vec<&(String, String)> = optional
+        .iter()
+        .filter(|(key, _)| !manifest::optional_dependency_reachable(&enabled, key))
+        .collect();
+    let implicit: Vec<&(String, String)> = optional
+        .iter()
+        .filter(|(key, _)| {
+            manifest::has_implicit_feature(&enabled, key)
+                && !enabled
+                    .iter()
+                    .any(|entry| manifest::feature_enables(entry, key))
+        })
+        .collect();
+
+    if unreachable.is_empty() {
+        println!(
+            "✓ All {} optional dependencies are enabled by a feature.",
+            optional.len()
+        );
+        for (key, section) in &implicit {
+            println!(
+                "  - {} in [{}] only through its implicit feature (`--features {}`)",
+                key, section, key
+            );
+        }
+        return Ok(());
+    }
+
+    println!(
+        "Unreachable optional dependencies (referenced as `dep:` so cargo adds no implicit feature, and no feature enables them):"
+    );
+    for (key, section) in &unreachable {
+        println!("  - {} in [{}]", key, section);
+    }
+
+    println!("\nAdd a feature for each one, for example:");
+    println!("[features]");
+    for (key, _) in &unreachable {
+        println!("{0} = [\"dep:{0}\"]", key);
+    }
+    println!("\nOr remove `optional = true` if they should always be built.");
+
+    let names: Vec<String> = unreachable.iter().map(|(key, _)| key.clone()).collect();
+    Err(format!("unreachable optional dependencies: {}", backticked(&names)).into())
+}
@@ -0,0 +1,137 @@
+use crate::commands::{check_features, feature_combinations};
+use crate::manifest;
+
+pub fn run(max_combos: usize, thorough: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let features = manifest::feature_names(&doc);
+    if features.is_empty() {
+        println!("No features declared in [features].");
+        return Ok(());
+    }
+
+    let has_default = doc
+        .get("features")
+        .and_then(|features| features.get("default"))
+        .is_some();
+    let combinations = combinations(&features, has_default, thorough, max_combos);
+    println!("Checking {} feature combinations...\n", combinations.len());
+
+    let mut failures = Vec::new();
+    for combination in &combinations {
+        let names: Vec<&str> = combination.iter().map(String::as_str).collect();
+        match check_features(&names)? {
+            None => println!("✓ {}", label(&names)),
+            Some(error) => {
+                println!("✗ {}: {}", label(&names), error);
+                failures.push(names);
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        println!(
+            "\n✓ All {} feature combinations compile.",
+            combinations.len()
+        );
+        if !thorough && combinations.len() == max_combos {
+            println!("Run with --thorough to check every combination.");
+        }
+        return Ok(());
+    }
+
+    // Smaller failures are usually the cause of bigger ones, so shrink the
+    // smallest first and skip failures that contain a known minimal set.
+    failures.sort_by_key(Vec::len);
+    let mut minimal: Vec<Vec<&str>> = Vec::new();
+    for failure in failures.iter() {
+        if minimal
+            .iter()
+            .any(|m| m.iter().all(|f| failure.contains(f)))
+        {
+            continue;
+        }
+        println!(
+            "\nFinding the minimal failing set for {}...",
+            label(failure)
+        );
+        minimal.push(minimize(failure)?);
+    }
+
+    println!("\nMinimal failing feature combinations:");
+    for combination in &minimal {
+        println!("  - {}", label(combination));
+    }
+
+    Err(format!(
+        "{} of {} feature combinations failed to compile",
+        failures.len(),
+        combinations.len()
+    )
+    .into())
+}
+
+/// Breadth-first combinations under the shared cap, with `default` and all
+/// features together moved up next to the single features: after those, the
+/// largest combinations are the first to be cut. `thorough` lifts the cap.
+fn combinations(
+    features: &[String],
+    has_default: bool,
+    thorough: bool,
+    max_combos: usize,
+) -> Vec<Vec<String>> {
+    let max = (!thorough).then_some(max_combos);
+    let mut combinations = feature_combinations(features, max);
+
+    // No features first, then the single features
+    let mut position = (1 + features.len()).min(combinations.len());
+    if has_default {
+        combinations.insert(1.min(combinations.len()), vec!["default".to_string()]);
+        position += 1;
+    }
+    if features.len() > 1 && !combinations.contains(&features.to_vec()) {
+        combinations.insert(position.min(combinations.len()), features.to_vec());
+    }
+
+    if let Some(max) = max {
+        combinations.truncate(max);
+    }
+    combinations
+}
+
+/// Binary search for a smallest failing subset: keep whichever half still
+/// fails on its own, and when neither does, drop features one at a time.
+fn minimize<'a>(failing: &[&'a str]) -> Result<Vec<&'a str>, Box<dyn std::error::Error>> {
+    let mut current = failing.to_vec();
+
+    while current.len() > 1 {
+        let (left, right) = current.split_at(current.len() / 2);
+        if check_features(left)?.is_some() {
+            current = left.to_vec();
+        } else if check_features(right)?.is_some() {
+            current = right.to_vec();
+        } else {
+            break;
+        }
+    }
+
+    let mut index = 0;
+    while index < current.len() && current.len() > 1 {
+        let mut without = current.clone();
+        without.remove(index);
+        if check_features(&without)?.is_some() {
+            current = without;
+        } else {
+            index += 1;
+        }
+    }
+
+    Ok(current)
+}
+
+fn label(features: &[&str]) -> String {
+    if features.is_empty() {
+        "(no features)".to_string()
+    } else {
+        features.join(",")
+    }
+}
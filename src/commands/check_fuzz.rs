@@ -0,0 +1,140 @@
+use crate::manifest;
+use std::fs;
+use std::path::Path;
+use toml_edit::Item;
+
+const FUZZ_DIR: &str = "fuzz";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let fuzz_manifest = Path::new(FUZZ_DIR).join("Cargo.toml");
+    if !Path::new(FUZZ_DIR).is_dir() {
+        println!("No fuzz/ directory found; run `cargo fuzz init` to add fuzz targets.");
+        return Ok(());
+    }
+    if !fuzz_manifest.exists() {
+        return Err("fuzz/ exists but has no Cargo.toml; run `cargo fuzz init`".into());
+    }
+
+    let fuzz_doc = manifest::read_manifest_at(&fuzz_manifest)?;
+    let root_doc = manifest::read_manifest()?;
+    let mut problems = 0;
+
+    let dependencies = fuzz_doc.get("dependencies").and_then(Item::as_table_like);
+    let has = |name: &str| {
+        dependencies.is_some_and(|table| {
+            table
+                .iter()
+                .any(|(key, item)| manifest::package_name(key, item) == name)
+        })
+    };
+    if has("libfuzzer-sys") {
+        println!("✓ fuzz/Cargo.toml depends on libfuzzer-sys");
+    } else {
+        problems += 1;
+        println!("✗ fuzz/Cargo.toml is missing libfuzzer-sys in [dependencies]");
+    }
+
+    let crate_name = root_doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str);
+    if let Some(name) = crate_name {
+        if has(name) {
+            println!("✓ fuzz/Cargo.toml depends on {}", name);
+        } else {
+            problems += 1;
+            println!(
+                "✗ fuzz/Cargo.toml doesn't depend on {}; add `{} = {{ path = \"..\" }}`",
+                name, name
+            );
+        }
+    }
+
+    // cargo-fuzz gives fuzz/ its own `[workspace]` so it stays out of the
+    // parent's; otherwise the parent has to list it as a member
+    let own_workspace = fuzz_doc.contains_key("workspace");
+    let members: Vec<String> = root_doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(Item::as_array)
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(|m| m.trim_end_matches('/').to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let excluded = root_doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("exclude"))
+        .and_then(Item::as_array)
+        .is_some_and(|exclude| exclude.iter().any(|e| e.as_str() == Some(FUZZ_DIR)));
+    if own_workspace {
+        println!("✓ fuzz/Cargo.toml declares its own [workspace]");
+    } else if members.iter().any(|m| m == FUZZ_DIR) {
+        println!("✓ fuzz is a member of the workspace");
+    } else if root_doc.contains_key("workspace") && !excluded {
+        problems += 1;
+        println!(
+            "✗ fuzz isn't a workspace member; add it to [workspace] members, or add an empty [workspace] to fuzz/Cargo.toml"
+        );
+    }
+
+    let targets = fuzz_doc
+        .get("bin")
+        .and_then(Item::as_array_of_tables)
+        .map(|bins| bins.len())
+        .unwrap_or(0);
+    if targets == 0 {
+        problems += 1;
+        println!(
+            "✗ fuzz/Cargo.toml declares no [[bin]] fuzz targets; add one with `cargo fuzz add <name>`"
+        );
+    } else {
+        println!("✓ {} fuzz target(s) declared", targets);
+    }
+
+    let root_ignore = fs::read_to_string(".gitignore").unwrap_or_default();
+    let fuzz_ignore =
+        fs::read_to_string(Path::new(FUZZ_DIR).join(".gitignore")).unwrap_or_default();
+    if ignores(&root_ignore, &fuzz_ignore, "target") {
+        println!("✓ fuzz/target is ignored by git");
+    } else {
+        problems += 1;
+        println!("✗ fuzz/target isn't ignored; add `target` to fuzz/.gitignore");
+    }
+    if ignores(&root_ignore, &fuzz_ignore, "corpus") {
+        problems += 1;
+        println!(
+            "✗ fuzz/corpus is ignored by git; remove `corpus` from .gitignore so the corpus is committed"
+        );
+    } else {
+        println!("✓ fuzz/corpus can be committed");
+    }
+
+    if problems == 0 {
+        println!("\n✓ Fuzzing setup is complete.");
+    }
+    Ok(())
+}
+
+/// Whether `fuzz/<dir>` is ignored, either by fuzz/.gitignore or by a root
+/// pattern such as `target` (which matches at any depth) or `fuzz/target`.
+fn ignores(root_ignore: &str, fuzz_ignore: &str, dir: &str) -> bool {
+    let patterns = |content: &str| -> Vec<String> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+            .map(|line| line.trim_end_matches('/').to_string())
+            .collect()
+    };
+
+    let fuzz_path = format!("{}/{}", FUZZ_DIR, dir);
+    patterns(fuzz_ignore)
+        .iter()
+        .any(|p| p.trim_start_matches('/') == dir)
+        || patterns(root_ignore)
+            .iter()
+            .any(|p| p == dir || p.trim_start_matches('/') == fuzz_path)
+}
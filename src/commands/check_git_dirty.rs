@@ -0,0 +1,48 @@
+use std::process::Command;
+
+const DIRTY_WARNING: &str = "Working tree has uncommitted changes. Consider committing before running cargo tidy to make rollback easier.";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(changes) = uncommitted_changes() else {
+        println!("Not a git repository; nothing to check.");
+        return Ok(());
+    };
+
+    if changes.is_empty() {
+        println!("✓ Working tree is clean.");
+        return Ok(());
+    }
+
+    println!("Uncommitted changes:");
+    for change in &changes {
+        println!("  - {}", change);
+    }
+    Err(DIRTY_WARNING.into())
+}
+
+/// Printed before commands that modify the project. It's only a warning:
+/// the command still runs.
+pub fn warn_if_dirty() {
+    if uncommitted_changes().is_some_and(|changes| !changes.is_empty()) {
+        println!("Warning: {}", DIRTY_WARNING);
+        println!("(Pass --allow-dirty to silence this warning.)\n");
+    }
+}
+
+/// `git status --porcelain` lines, or `None` outside a git repository.
+fn uncommitted_changes() -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let changes = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    Some(changes)
+}
@@ -0,0 +1,132 @@
+use crate::{is_std_module, manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Allocator types and the crates that usually provide them, for when the
+/// type comes in through a glob or re-export we can't trace.
+const KNOWN_ALLOCATORS: &[(&str, &str)] = &[
+    ("MiMalloc", "mimalloc"),
+    ("Jemalloc", "tikv-jemallocator"),
+    ("WeeAlloc", "wee_alloc"),
+    ("Talck", "talc"),
+    ("Snmalloc", "snmalloc-rs"),
+    ("RpMalloc", "rpmalloc"),
+    ("LockedHeap", "linked_list_allocator"),
+    ("Dlmalloc", "dlmalloc"),
+];
+
+/// A `#[global_allocator]` static.
+struct Allocator {
+    location: String,
+    ty: String,
+    /// Crate the type comes from, `None` for types defined in this crate or std
+    krate: Option<String>,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let static_regex = Regex::new(r"static\s+(?:mut\s+)?[A-Za-z_][A-Za-z0-9_]*\s*:\s*([^=;]+)")?;
+    let use_regex =
+        Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+(?:::)?([A-Za-z_][A-Za-z0-9_]*)::(.+);")?;
+
+    let mut allocators = Vec::new();
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim() != "#[global_allocator]" {
+                continue;
+            }
+            // The static may follow other attributes or sit on the same line
+            let Some(ty) = lines[index..]
+                .iter()
+                .find_map(|line| static_regex.captures(line))
+                .map(|cap| cap[1].trim().to_string())
+            else {
+                continue;
+            };
+
+            let krate = match ty.split_once("::") {
+                Some((root, _)) => Some(root.trim_start_matches(':').to_string()),
+                None => {
+                    // Bare type name: find the `use` that brought it in scope
+                    let name = ty.split('<').next().unwrap_or(&ty).trim();
+                    lines
+                        .iter()
+                        .filter_map(|line| use_regex.captures(line))
+                        .find(|cap| imports(&cap[2], name))
+                        .map(|cap| cap[1].to_string())
+                        .or_else(|| {
+                            KNOWN_ALLOCATORS
+                                .iter()
+                                .find(|(known, _)| *known == name)
+                                .map(|(_, krate)| krate.to_string())
+                        })
+                }
+            }
+            .filter(|krate| {
+                !is_std_module(krate) && !["crate", "self", "super"].contains(&krate.as_str())
+            });
+
+            allocators.push(Allocator {
+                location: format!("{}:{}", file.display(), index + 1),
+                ty,
+                krate,
+            });
+        }
+    }
+
+    if allocators.is_empty() {
+        println!("No #[global_allocator] found in src/.");
+        return Ok(());
+    }
+
+    let doc = manifest::read_manifest()?;
+    let dependencies: Vec<String> = manifest::dependency_tables(&doc)
+        .iter()
+        .filter(|(section, _)| !section.ends_with("dev-dependencies"))
+        .flat_map(|(_, table)| table.iter().map(|(key, _)| key.replace('-', "_")))
+        .collect();
+
+    let mut missing = Vec::new();
+    for allocator in &allocators {
+        match &allocator.krate {
+            Some(krate) if !dependencies.contains(&krate.replace('-', "_")) => {
+                println!(
+                    "✗ {}: {} comes from {}, which isn't in [dependencies]",
+                    allocator.location, allocator.ty, krate
+                );
+                missing.push(krate.clone());
+            }
+            Some(krate) => println!("✓ {}: {} from {}", allocator.location, allocator.ty, krate),
+            None => println!("✓ {}: {}", allocator.location, allocator.ty),
+        }
+    }
+
+    if allocators.len() > 1 {
+        println!(
+            "\n✗ {} #[global_allocator] definitions found; linking more than one into a binary fails:",
+            allocators.len()
+        );
+        for allocator in &allocators {
+            println!("  - {} ({})", allocator.location, allocator.ty);
+        }
+    }
+
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        println!("\nAdd them with: cargo add {}", missing.join(" "));
+    }
+
+    Ok(())
+}
+
+/// Whether the path after `use krate::` brings `name` into scope, either
+/// directly or inside a `{...}` group.
+fn imports(path: &str, name: &str) -> bool {
+    path.split([':', '{', '}', ','])
+        .map(str::trim)
+        .any(|segment| segment == name || segment.ends_with(&format!(" as {}", name)))
+}
@@ -0,0 +1,291 @@
+use crate::{manifest, metadata, source};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Wrappers with longer bodies are unlikely to be worth inlining.
+const MAX_BODY_LINES: usize = 3;
+
+/// A small public function that forwards to a dependency.
+struct Wrapper {
+    location: String,
+    name: String,
+    dependency: String,
+    /// Benchmarks whose code calls the wrapper, with their time per iteration
+    /// in nanoseconds.
+    timings: Vec<(String, f64)>,
+}
+
+/// A measured benchmark and the part of its bench file that runs it.
+struct Benchmark {
+    id: String,
+    nanos: f64,
+    code: String,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    if !Path::new("src/lib.rs").exists() {
+        println!("No src/lib.rs; #[inline] only matters for functions called from other crates.");
+        return Ok(());
+    }
+
+    let benches: Vec<(String, String)> = source::rust_files(Path::new("benches"))
+        .into_iter()
+        .filter_map(|file| {
+            let content = fs::read_to_string(&file).ok()?;
+            Some((file.display().to_string(), content))
+        })
+        .collect();
+    if benches.is_empty() {
+        println!("No benches/ directory found; inline hints come from benchmark timings.");
+        println!("Add libtest #[bench] or criterion benchmarks that call your wrappers.");
+        return Ok(());
+    }
+
+    let doc = manifest::read_manifest()?;
+    let dependencies: Vec<String> = manifest::dependency_tables(&doc)
+        .iter()
+        .filter(|(section, _)| !section.ends_with("dev-dependencies"))
+        .flat_map(|(_, table)| table.iter().map(|(key, _)| key.replace('-', "_")))
+        .collect();
+
+    // Generic functions are already instantiated in the caller's crate, so
+    // only non-generic ones miss out on cross-crate inlining
+    let fn_regex =
+        Regex::new(r"^\s*pub(?:\([^)]*\))?\s+(?:const\s+)?fn\s+([A-Za-z_][A-Za-z0-9_]*)\s*\(")?;
+
+    let mut wrappers = Vec::new();
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some(cap) = fn_regex.captures(line) else {
+                continue;
+            };
+            if has_inline_attribute(&lines[..index]) {
+                continue;
+            }
+            let Some(body) = source::function_body(&lines[index..]) else {
+                continue;
+            };
+            if body.len() > MAX_BODY_LINES {
+                continue;
+            }
+            let Some(dependency) = dependencies
+                .iter()
+                .find(|dep| body.iter().any(|line| line.contains(&format!("{}::", dep))))
+            else {
+                continue;
+            };
+
+            wrappers.push(Wrapper {
+                location: format!("{}:{}", file.display(), index + 1),
+                name: cap[1].to_string(),
+                dependency: dependency.clone(),
+                timings: Vec::new(),
+            });
+        }
+    }
+
+    if wrappers.is_empty() {
+        println!("No small public wrappers around dependency functions without #[inline].");
+        return Ok(());
+    }
+
+    println!(
+        "Running `cargo bench` to time {} candidate wrapper(s)...\n",
+        wrappers.len()
+    );
+    let benchmarks = run_benchmarks(&benches)?;
+    if benchmarks.is_empty() {
+        return Err("cargo bench produced no libtest or criterion timings".into());
+    }
+
+    for wrapper in &mut wrappers {
+        let call = Regex::new(&format!(r"\b{}\s*\(", regex::escape(&wrapper.name)))?;
+        wrapper.timings = benchmarks
+            .iter()
+            .filter(|benchmark| call.is_match(&benchmark.code))
+            .map(|benchmark| (benchmark.id.clone(), benchmark.nanos))
+            .collect();
+        wrapper
+            .timings
+            .sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    }
+
+    let (mut measured, unmeasured): (Vec<&Wrapper>, Vec<&Wrapper>) = wrappers
+        .iter()
+        .partition(|wrapper| !wrapper.timings.is_empty());
+    // The slowest benchmark a wrapper sits in ranks it
+    measured.sort_by(|a, b| b.timings[0].1.total_cmp(&a.timings[0].1));
+
+    if measured.is_empty() {
+        println!(
+            "No benchmark calls one of the candidate wrappers, so there is no timing to go on."
+        );
+    } else {
+        println!(
+            "Consider #[inline] on these wrappers, ranked by the benchmarks that call them:\n"
+        );
+        for wrapper in &measured {
+            println!(
+                "  - {} ({}, calls into {})",
+                wrapper.name, wrapper.location, wrapper.dependency
+            );
+            for (id, nanos) in &wrapper.timings {
+                println!("      {}: {}/iter", id, format_nanos(*nanos));
+            }
+        }
+        println!(
+            "\nRe-run `cargo bench` after adding #[inline] and keep it only where the time drops."
+        );
+    }
+
+    if !unmeasured.is_empty() {
+        println!("\nNot called by any benchmark, so not ranked:");
+        for wrapper in &unmeasured {
+            println!("  - {} ({})", wrapper.name, wrapper.location);
+        }
+    }
+    Ok(())
+}
+
+/// Runs `cargo bench` and collects libtest timings from its output and
+/// criterion timings from the reports it just wrote.
+fn run_benchmarks(
+    benches: &[(String, String)],
+) -> Result<Vec<Benchmark>, Box<dyn std::error::Error>> {
+    let started = SystemTime::now();
+    let output = Command::new("cargo").arg("bench").output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = stderr
+            .lines()
+            .find(|line| line.starts_with("error"))
+            .unwrap_or("benchmarks failed");
+        return Err(format!("cargo bench failed: {}", error).into());
+    }
+
+    let mut benchmarks = Vec::new();
+
+    // `test parse_short ... bench:       1,234.56 ns/iter (+/- 12.34)`
+    let libtest_regex = Regex::new(r"(?m)^test (\S+)\s+\.\.\. bench:\s+([\d,.]+) ns/iter")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for cap in libtest_regex.captures_iter(&stdout) {
+        let Ok(nanos) = cap[2].replace(',', "").parse::<f64>() else {
+            continue;
+        };
+        let name = cap[1].rsplit("::").next().unwrap_or(&cap[1]);
+        let fn_regex = Regex::new(&format!(
+            r"^\s*(?:pub\s+)?fn\s+{}\s*\(",
+            regex::escape(name)
+        ))?;
+        for (_, content) in benches {
+            let lines: Vec<&str> = content.lines().collect();
+            if let Some(body) = lines
+                .iter()
+                .position(|line| fn_regex.is_match(line))
+                .and_then(|index| source::function_body(&lines[index..]))
+            {
+                benchmarks.push(Benchmark {
+                    id: cap[1].to_string(),
+                    nanos,
+                    code: body.join("\n"),
+                });
+            }
+        }
+    }
+
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let criterion_dir =
+        Path::new(metadata["target_directory"].as_str().unwrap_or("target")).join("criterion");
+    for (id, function_id, nanos) in criterion_reports(&criterion_dir, started) {
+        // The benchmark's id is a string literal in the bench file; its code
+        // runs up to the next benchmark defined after it
+        let literal = format!("\"{}\"", function_id);
+        for (_, content) in benches {
+            let Some(start) = content.find(&literal) else {
+                continue;
+            };
+            let rest = &content[start + literal.len()..];
+            let end = ["bench_function(", "bench_with_input(", "benchmark_group("]
+                .iter()
+                .filter_map(|next| rest.find(next))
+                .min()
+                .unwrap_or(rest.len());
+            benchmarks.push(Benchmark {
+                id: id.clone(),
+                nanos,
+                code: rest[..end].to_string(),
+            });
+        }
+    }
+
+    Ok(benchmarks)
+}
+
+/// `(full id, id as written in the bench file, mean ns)` for every criterion
+/// report under `dir` written since `since`.
+fn criterion_reports(dir: &Path, since: SystemTime) -> Vec<(String, String, f64)> {
+    let mut reports = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return reports;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            let estimates = path.join("estimates.json");
+            let fresh = fs::metadata(&estimates)
+                .and_then(|meta| meta.modified())
+                .is_ok_and(|modified| modified >= since);
+            let read = |file: &str| -> Option<Value> {
+                serde_json::from_str(&fs::read_to_string(path.join(file)).ok()?).ok()
+            };
+            if let (true, Some(benchmark), Some(estimates)) =
+                (fresh, read("benchmark.json"), read("estimates.json"))
+                && let (Some(full_id), Some(nanos)) = (
+                    benchmark["full_id"].as_str(),
+                    estimates["mean"]["point_estimate"].as_f64(),
+                )
+            {
+                // Inside a group the literal is the function id, otherwise
+                // the group id is the name given to bench_function
+                let written = benchmark["function_id"]
+                    .as_str()
+                    .or_else(|| benchmark["group_id"].as_str())
+                    .unwrap_or(full_id);
+                reports.push((full_id.to_string(), written.to_string(), nanos));
+            }
+        } else {
+            reports.extend(criterion_reports(&path, since));
+        }
+    }
+    reports
+}
+
+fn format_nanos(nanos: f64) -> String {
+    if nanos >= 1_000_000.0 {
+        format!("{:.2} ms", nanos / 1_000_000.0)
+    } else if nanos >= 1_000.0 {
+        format!("{:.2} µs", nanos / 1_000.0)
+    } else {
+        format!("{:.1} ns", nanos)
+    }
+}
+
+/// Whether the attributes directly above a function include `#[inline]`.
+fn has_inline_attribute(before: &[&str]) -> bool {
+    before
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("///"))
+        .any(|line| line.starts_with("#[inline"))
+}
@@ -0,0 +1,145 @@
+use crate::{manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Crates built on io_uring, which needs Linux 5.1 or newer.
+const URING_CRATES: &[&str] = &["io-uring", "tokio-uring", "rio", "glommio"];
+
+const LINUX_CFG: &str = "cfg(target_os = \"linux\")";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+
+    // (key, section header, spec) of every io_uring dependency
+    let mut found = Vec::new();
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            if URING_CRATES.contains(&manifest::package_name(key, item).as_str()) {
+                found.push((key.to_string(), section.clone(), inline_spec(item)));
+            }
+        }
+    }
+
+    if found.is_empty() {
+        println!("No io_uring crates found in Cargo.toml.");
+        return Ok(());
+    }
+
+    let mut problems = 0;
+    for (key, section, spec) in &found {
+        if is_linux_only(section) {
+            println!("✓ {} is in [{}]", key, section);
+            continue;
+        }
+
+        problems += 1;
+        let kind = section.rsplit('.').next().unwrap_or(section);
+        println!(
+            "✗ {} is in [{}] but io_uring only exists on Linux; move it to:\n",
+            key, section
+        );
+        println!("    [target.'{}'.{}]", LINUX_CFG, kind);
+        println!("    {} = {}\n", key, spec);
+
+        let crate_name = key.replace('-', "_");
+        let unguarded = unguarded_uses(&crate_name)?;
+        if !unguarded.is_empty() {
+            println!("  and guard the code that uses it:\n");
+            for (location, line) in &unguarded {
+                println!("    // {}", location);
+                println!("    #[cfg(target_os = \"linux\")]");
+                println!("    {}\n", line);
+            }
+        }
+    }
+
+    if !documents_kernel_version(&doc)? {
+        problems += 1;
+        println!(
+            "✗ The README doesn't mention the minimum Linux kernel version; io_uring needs 5.1 or newer"
+        );
+    } else {
+        println!("✓ The README documents the minimum kernel version");
+    }
+
+    if problems == 0 {
+        println!("\n✓ io_uring dependencies are Linux-only and documented.");
+    }
+    Ok(())
+}
+
+/// Whether a dependency section header only applies on Linux: the
+/// `target_os = "linux"` cfg or a Linux target triple.
+fn is_linux_only(section: &str) -> bool {
+    let Some(target) = section
+        .strip_prefix("target.'")
+        .and_then(|rest| rest.rsplit_once("'."))
+        .map(|(target, _)| target)
+    else {
+        return false;
+    };
+    let compact = |s: &str| s.split_whitespace().collect::<String>();
+    if target.starts_with("cfg(") {
+        compact(target) == compact(LINUX_CFG)
+    } else {
+        target.contains("-linux")
+    }
+}
+
+/// The dependency spec as the value of a `key = ...` line. A
+/// `[dependencies.key]` table becomes an inline table.
+fn inline_spec(item: &Item) -> String {
+    match item {
+        Item::Table(table) => table.clone().into_inline_table().to_string(),
+        other => other.to_string(),
+    }
+    .trim()
+    .to_string()
+}
+
+/// `use` statements of `crate_name` that aren't directly under a Linux cfg,
+/// as (file:line, statement).
+fn unguarded_uses(crate_name: &str) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let use_regex = Regex::new(&format!(
+        r"^(?:pub(?:\([^)]*\))?\s+)?use\s+(?:::)?{}\b",
+        regex::escape(crate_name)
+    ))?;
+
+    let mut uses = Vec::new();
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        let mut guarded = false;
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with("#[cfg(") {
+                guarded = line.contains("linux");
+                continue;
+            }
+            if use_regex.is_match(line) && !guarded {
+                uses.push((
+                    format!("{}:{}", file.display(), number + 1),
+                    line.to_string(),
+                ));
+            }
+            guarded = false;
+        }
+    }
+    Ok(uses)
+}
+
+/// Whether the README (or the `readme` field's file) names a kernel version.
+fn documents_kernel_version(doc: &DocumentMut) -> Result<bool, Box<dyn std::error::Error>> {
+    let readme = doc
+        .get("package")
+        .and_then(|package| package.get("readme"))
+        .and_then(Item::as_str)
+        .unwrap_or("README.md");
+    let Ok(content) = fs::read_to_string(readme) else {
+        return Ok(false);
+    };
+
+    let kernel_regex = Regex::new(r"(?i)(kernel|linux)\D{0,20}\d+\.\d+")?;
+    Ok(kernel_regex.is_match(&content))
+}
@@ -0,0 +1,174 @@
+use crate::metadata;
+use serde_json::Value;
+
+/// A `-sys` crate that links a library from the system.
+struct NativeLibrary {
+    krate: &'static str,
+    debian: &'static str,
+    /// Homebrew formula, if the library exists on macOS
+    macos: Option<&'static str>,
+    /// Feature that builds the library from source instead
+    bundled_feature: Option<&'static str>,
+}
+
+const NATIVE_LIBRARIES: &[NativeLibrary] = &[
+    NativeLibrary {
+        krate: "openssl-sys",
+        debian: "libssl-dev and pkg-config",
+        macos: Some("openssl"),
+        bundled_feature: Some("vendored"),
+    },
+    NativeLibrary {
+        krate: "libsqlite3-sys",
+        debian: "libsqlite3-dev",
+        macos: Some("sqlite"),
+        bundled_feature: Some("bundled"),
+    },
+    NativeLibrary {
+        krate: "libgit2-sys",
+        debian: "libgit2-dev",
+        macos: Some("libgit2"),
+        bundled_feature: Some("vendored"),
+    },
+    NativeLibrary {
+        krate: "libssh2-sys",
+        debian: "libssh2-1-dev",
+        macos: Some("libssh2"),
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "curl-sys",
+        debian: "libcurl4-openssl-dev",
+        macos: Some("curl"),
+        bundled_feature: Some("static-curl"),
+    },
+    NativeLibrary {
+        krate: "libz-sys",
+        debian: "zlib1g-dev",
+        macos: Some("zlib"),
+        bundled_feature: Some("static"),
+    },
+    NativeLibrary {
+        krate: "pq-sys",
+        debian: "libpq-dev",
+        macos: Some("libpq"),
+        bundled_feature: Some("bundled"),
+    },
+    NativeLibrary {
+        krate: "mysqlclient-sys",
+        debian: "libmysqlclient-dev",
+        macos: Some("mysql-client"),
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "libdbus-sys",
+        debian: "libdbus-1-dev",
+        macos: Some("dbus"),
+        bundled_feature: Some("vendored"),
+    },
+    NativeLibrary {
+        krate: "alsa-sys",
+        debian: "libasound2-dev",
+        macos: None,
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "libudev-sys",
+        debian: "libudev-dev",
+        macos: None,
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "gtk-sys",
+        debian: "libgtk-3-dev",
+        macos: Some("gtk+3"),
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "freetype-sys",
+        debian: "libfreetype6-dev",
+        macos: Some("freetype"),
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "x11",
+        debian: "libx11-dev",
+        macos: None,
+        bundled_feature: None,
+    },
+    NativeLibrary {
+        krate: "pcap-sys",
+        debian: "libpcap-dev",
+        macos: Some("libpcap"),
+        bundled_feature: None,
+    },
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&[])?;
+    let mut warnings = 0;
+
+    for package in metadata::packages(&metadata) {
+        let (Some(id), Some(name)) = (package["id"].as_str(), package["name"].as_str()) else {
+            continue;
+        };
+        let features = enabled_features(&metadata, id);
+
+        if let Some(library) = NATIVE_LIBRARIES
+            .iter()
+            .find(|library| library.krate == name)
+        {
+            if let Some(feature) = library.bundled_feature
+                && features.iter().any(|f| f == feature)
+            {
+                continue;
+            }
+            warnings += 1;
+            let macos = match library.macos {
+                Some(formula) => format!("{} on macOS (via Homebrew)", formula),
+                None => "and isn't available on macOS".to_string(),
+            };
+            println!(
+                "✗ {} requires {} on Debian/Ubuntu, {}.",
+                name, library.debian, macos
+            );
+            if let Some(feature) = library.bundled_feature {
+                println!(
+                    "    Or enable its `{}` feature to build the library from source.",
+                    feature
+                );
+            }
+        } else if let Some(links) = package["links"].as_str() {
+            // Anything else with `links` may need a system library too
+            println!(
+                "Note: {} links the native library `{}`; check its README for install steps",
+                name, links
+            );
+        }
+    }
+
+    if warnings == 0 {
+        println!("✓ No dependencies need native libraries from the known list.");
+    } else {
+        println!("\nThese libraries must be installed separately before the project builds.");
+    }
+
+    Ok(())
+}
+
+/// Features the resolver enabled for a package.
+fn enabled_features(metadata: &Value, id: &str) -> Vec<String> {
+    metadata["resolve"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|node| node["id"] == id)
+        .and_then(|node| node["features"].as_array())
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|f| f.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
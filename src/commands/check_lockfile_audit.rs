@@ -0,0 +1,80 @@
+use crate::cratesio;
+use crate::lockfile::{self, LOCKFILE_PATH};
+use std::path::Path;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let packages: Vec<_> = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?
+        .into_iter()
+        .filter(|package| package.is_from_crates_io())
+        .collect();
+
+    println!(
+        "Verifying checksums of {} locked crates.io packages...\n",
+        packages.len()
+    );
+
+    let mut mismatches = 0;
+    let mut unchecked = 0;
+    for package in &packages {
+        let Some(locked) = &package.checksum else {
+            // Format v1 keeps checksums in [metadata]; check-lockfile-v2 upgrades it
+            println!(
+                "✗ {} {} has no checksum in Cargo.lock",
+                package.name, package.version
+            );
+            unchecked += 1;
+            continue;
+        };
+
+        // The index's `cksum` is the same SHA-256 cargo verifies downloads
+        // against. It's fetched fresh: a cached copy could be stale or planted.
+        let entries = match cratesio::index_entries(&package.name) {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("✗ Could not fetch {}: {}", package.name, e);
+                unchecked += 1;
+                continue;
+            }
+        };
+        let Some(expected) = entries
+            .iter()
+            .find(|entry| entry["vers"] == package.version.as_str())
+            .and_then(|entry| entry["cksum"].as_str())
+        else {
+            println!(
+                "✗ {} {} is not in the crates.io index",
+                package.name, package.version
+            );
+            unchecked += 1;
+            continue;
+        };
+
+        if expected != locked {
+            mismatches += 1;
+            println!(
+                "CHECKSUM MISMATCH for {} {} — possible supply chain attack.",
+                package.name, package.version
+            );
+            println!("    Cargo.lock: {}", locked);
+            println!("    crates.io:  {}", expected);
+        }
+    }
+
+    if mismatches > 0 {
+        println!("\nDon't build this project until the mismatches are explained.");
+        println!(
+            "Delete the affected [[package]] entries and run `cargo update` to re-resolve them."
+        );
+        return Err(format!("{} checksum mismatch(es) in Cargo.lock", mismatches).into());
+    }
+
+    if unchecked == 0 {
+        println!("✓ Every checksum matches crates.io.");
+    } else {
+        println!(
+            "\nNo mismatches among the {} packages that could be checked.",
+            packages.len() - unchecked
+        );
+    }
+    Ok(())
+}
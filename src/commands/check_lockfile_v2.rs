@@ -0,0 +1,82 @@
+use crate::commands::confirm;
+use crate::lockfile::{self, LOCKFILE_PATH};
+use crate::metadata;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use toml_edit::{DocumentMut, value};
+
+/// The oldest format with the `version = N` header.
+const TARGET_VERSION: i64 = 3;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let version = lockfile::lockfile_version(Path::new(LOCKFILE_PATH))?;
+
+    if version >= TARGET_VERSION {
+        println!("✓ Cargo.lock uses format v{}.", version);
+        return Ok(());
+    }
+
+    println!(
+        "✗ Cargo.lock uses format v{}; v3 or newer is recommended.",
+        version
+    );
+    println!(
+        "Older formats store checksums less reliably and are rewritten by newer cargo versions."
+    );
+
+    if !confirm("Regenerate Cargo.lock in the current cargo format?") {
+        return Ok(());
+    }
+
+    let original = fs::read_to_string(LOCKFILE_PATH)?;
+    let upgraded = match regenerate(&original) {
+        Ok(upgraded) => upgraded,
+        Err(e) => {
+            fs::write(LOCKFILE_PATH, &original)?;
+            return Err(e);
+        }
+    };
+    println!("✓ Cargo.lock upgraded from v{} to v{}", version, upgraded);
+    Ok(())
+}
+
+/// Rewrites Cargo.lock in the current cargo format and returns the new
+/// version. The caller restores `original` when this fails.
+fn regenerate(original: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let before = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?;
+
+    // Cargo only rewrites Cargo.lock when the resolve changes, so an
+    // up-to-date lockfile keeps its old format. Bump the header and let cargo
+    // re-serialize the file; `--workspace` keeps every dependency version.
+    let mut doc = original.parse::<DocumentMut>()?;
+    doc.insert("version", value(TARGET_VERSION));
+    let edited = doc.to_string();
+    fs::write(LOCKFILE_PATH, &edited)?;
+
+    let output = Command::new("cargo")
+        .args(["update", "--workspace"])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("cargo update failed: {}", stderr.trim()).into());
+    }
+
+    // The header alone is ours; only a file cargo wrote itself counts
+    if fs::read_to_string(LOCKFILE_PATH)? == edited {
+        return Err("cargo did not rewrite Cargo.lock".into());
+    }
+    let after = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?;
+    if after.len() != before.len() {
+        return Err(format!(
+            "Cargo.lock lists {} packages after regenerating it, {} before",
+            after.len(),
+            before.len()
+        )
+        .into());
+    }
+    // `--locked` fails if cargo would still change the file
+    metadata::cargo_metadata(&["--locked"])?;
+
+    lockfile::lockfile_version(Path::new(LOCKFILE_PATH))
+}
@@ -0,0 +1,108 @@
+use crate::{is_std_module, manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use toml_edit::Item;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let enabled = manifest::feature_entries(&doc);
+
+    // How each dependency is declared, keyed by the name used in paths
+    let mut declarations: Vec<(String, String, bool)> = Vec::new();
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            let optional = item
+                .as_table_like()
+                .and_then(|spec| spec.get("optional"))
+                .and_then(Item::as_bool)
+                .unwrap_or(false);
+            declarations.push((key.replace('-', "_"), section.clone(), optional));
+        }
+    }
+
+    let reexport_regex = Regex::new(r"^\s*pub\s+use\s+(?:::)?([a-zA-Z_][a-zA-Z0-9_]*)::(.+?);")?;
+
+    let mut reexports = 0;
+    let mut problems = Vec::new();
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some(cap) = reexport_regex.captures(line) else {
+                continue;
+            };
+            let krate = &cap[1];
+            if is_std_module(krate) || ["crate", "self", "super"].contains(&krate) {
+                continue;
+            }
+            reexports += 1;
+            let item = format!("{}::{}", krate, &cap[2]);
+            let location = format!("{}:{}", file.display(), index + 1);
+
+            if !has_doc_inline(&lines[..index]) {
+                problems.push(format!(
+                    "{}: `pub use {}` is missing #[doc(inline)]",
+                    location, item
+                ));
+            }
+
+            let in_dependencies = declarations
+                .iter()
+                .filter(|(name, _, _)| name == krate)
+                .find(|(_, section, _)| {
+                    section == "dependencies" || section.ends_with(".dependencies")
+                });
+            match in_dependencies {
+                None if declarations.iter().any(|(name, _, _)| name == krate) => {
+                    problems.push(format!(
+                        "{}: `{}` is re-exported but only declared in [dev-dependencies] or [build-dependencies]",
+                        location, krate
+                    ));
+                }
+                None => problems.push(format!(
+                    "{}: `{}` is re-exported but isn't a dependency",
+                    location, krate
+                )),
+                Some((_, _, true)) if !manifest::optional_dependency_reachable(&enabled, krate) => {
+                    problems.push(format!(
+                        "{}: `{}` is optional but no feature enables it",
+                        location, krate
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    if reexports == 0 {
+        println!("No re-exports from dependencies found.");
+        return Ok(());
+    }
+
+    if problems.is_empty() {
+        println!(
+            "✓ All {} re-exports from dependencies are documented inline and declared in [dependencies].",
+            reexports
+        );
+        return Ok(());
+    }
+
+    println!("Problems with re-exported dependency items:");
+    for problem in &problems {
+        println!("  - {}", problem);
+    }
+
+    Ok(())
+}
+
+/// Whether the attributes directly above a line include `#[doc(inline)]`.
+fn has_doc_inline(preceding: &[&str]) -> bool {
+    preceding
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("///"))
+        .any(|line| line.replace(' ', "").contains("#[doc(inline)]"))
+}
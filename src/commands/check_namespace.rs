@@ -0,0 +1,62 @@
+use crate::{is_std_module, manifest, metadata};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let mut conflicts = 0;
+
+    // The key is the name code refers to, so that's what can shadow std
+    for (section, table) in manifest::dependency_tables(&doc) {
+        for (key, item) in table.iter() {
+            let name = key.replace('-', "_");
+            if is_std_module(&name) {
+                conflicts += 1;
+                let package = manifest::package_name(key, item);
+                let renamed = if package != key {
+                    format!(" (renamed from {})", package)
+                } else {
+                    String::new()
+                };
+                println!(
+                    "✗ {}{} in [{}] has the same name as the standard library module `{}`",
+                    key, renamed, section, name
+                );
+            }
+        }
+    }
+
+    let metadata = metadata::cargo_metadata(&[])?;
+    let members = metadata::workspace_packages(&metadata);
+    let member_ids: Vec<&str> = members
+        .iter()
+        .filter_map(|member| member["id"].as_str())
+        .collect();
+    for member in &members {
+        let Some(name) = member["name"].as_str() else {
+            continue;
+        };
+        for package in metadata::packages(&metadata) {
+            let Some(id) = package["id"].as_str() else {
+                continue;
+            };
+            if package["name"] == name && !member_ids.contains(&id) {
+                conflicts += 1;
+                println!(
+                    "✗ Workspace member {} has the same name as the external dependency {} {}",
+                    name,
+                    name,
+                    package["version"].as_str().unwrap_or("?")
+                );
+            }
+        }
+    }
+
+    if conflicts == 0 {
+        println!("✓ No namespace conflicts found.");
+    } else {
+        println!(
+            "\nRename the dependency with `package = \"...\"` in Cargo.toml, or rename the member."
+        );
+    }
+
+    Ok(())
+}
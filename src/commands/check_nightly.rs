@@ -0,0 +1,82 @@
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::{Command, Output};
+
+/// Nightly builds get their own target directory so switching toolchains
+/// doesn't invalidate the regular build cache.
+const NIGHTLY_TARGET_DIR: &str = "target/cargo-tidy-nightly";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Checking with the stable toolchain...");
+    let stable = check("+stable", None)?;
+    if stable.status.success() {
+        println!("✓ Project compiles on stable; no nightly-only features in use.");
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&stable.stderr);
+    let unstable = unstable_usages(&stderr)?;
+
+    println!("Checking with the nightly toolchain...");
+    match check("+nightly", Some(NIGHTLY_TARGET_DIR)) {
+        Ok(nightly) if nightly.status.success() => {
+            println!("\n✗ Project only compiles on nightly.");
+        }
+        Ok(_) => println!("\nProject fails to compile on both stable and nightly."),
+        Err(_) => println!(
+            "\nNightly toolchain not available; install it with `rustup toolchain install nightly`."
+        ),
+    }
+
+    if unstable.is_empty() {
+        println!("No nightly-only features found in the stable errors; see `cargo +stable check`.");
+        return Ok(());
+    }
+
+    println!("Nightly-only features and APIs:");
+    for usage in &unstable {
+        println!("  - {}", usage);
+    }
+    Ok(())
+}
+
+fn check(toolchain: &str, target_dir: Option<&str>) -> Result<Output, Box<dyn std::error::Error>> {
+    let mut command = Command::new("cargo");
+    command.args([toolchain, "check", "--all-targets"]);
+    if let Some(dir) = target_dir {
+        command.env("CARGO_TARGET_DIR", dir);
+    }
+    Ok(command.output()?)
+}
+
+/// Unstable features named in stable's E0554 (`#![feature]` on stable) and
+/// E0658 (unstable feature or API) errors, with their locations.
+fn unstable_usages(stderr: &str) -> Result<BTreeSet<String>, Box<dyn std::error::Error>> {
+    let error_regex = Regex::new(r"error\[E0(554|658)\]: (.+)\n\s*--> (\S+)")?;
+    let mut usages = BTreeSet::new();
+
+    for cap in error_regex.captures_iter(stderr) {
+        let location = &cap[3];
+        let description = if &cap[1] == "554" {
+            source_line(location).unwrap_or_else(|| "#![feature(...)]".to_string())
+        } else {
+            cap[2].to_string()
+        };
+        usages.insert(format!("{}: {}", location, description));
+    }
+
+    Ok(usages)
+}
+
+/// The trimmed source line a `file:line:col` location points at.
+fn source_line(location: &str) -> Option<String> {
+    let mut parts = location.split(':');
+    let file = parts.next()?;
+    let line: usize = parts.next()?.parse().ok()?;
+    let content = fs::read_to_string(file).ok()?;
+    content
+        .lines()
+        .nth(line.checked_sub(1)?)
+        .map(|l| l.trim().to_string())
+}
@@ -0,0 +1,184 @@
+use crate::lockfile::{self, LOCKFILE_PATH};
+use crate::manifest;
+use std::path::Path;
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike, value};
+
+/// Every recommendation is the same setting, `opt-level = 3` in the dev profile.
+const PROFILE: &str = "dev";
+const KEY: &str = "opt-level";
+const LEVEL: i64 = 3;
+
+struct Override {
+    package: &'static str,
+    reason: &'static str,
+}
+
+/// Crates that are painfully slow in unoptimized builds, with the profile
+/// override that fixes it and why.
+const RECOMMENDED_OVERRIDES: &[Override] = &[
+    Override {
+        package: "sha2",
+        reason: "hashing is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "sha1",
+        reason: "hashing is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "md-5",
+        reason: "hashing is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "blake3",
+        reason: "hashing is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "aes",
+        reason: "encryption is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "aes-gcm",
+        reason: "encryption is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "chacha20poly1305",
+        reason: "encryption is orders of magnitude slower unoptimized",
+    },
+    Override {
+        package: "curve25519-dalek",
+        reason: "elliptic curve math is very slow unoptimized",
+    },
+    Override {
+        package: "argon2",
+        reason: "password hashing makes debug tests crawl",
+    },
+    Override {
+        package: "scrypt",
+        reason: "password hashing makes debug tests crawl",
+    },
+    Override {
+        package: "bcrypt",
+        reason: "password hashing makes debug tests crawl",
+    },
+    Override {
+        package: "image",
+        reason: "image decoding is very slow unoptimized",
+    },
+    Override {
+        package: "png",
+        reason: "image decoding is very slow unoptimized",
+    },
+    Override {
+        package: "miniz_oxide",
+        reason: "compression is very slow unoptimized",
+    },
+    Override {
+        package: "zstd-sys",
+        reason: "compression is very slow unoptimized",
+    },
+    Override {
+        package: "insta",
+        reason: "snapshot diffing is slow unoptimized (recommended by insta)",
+    },
+    Override {
+        package: "similar",
+        reason: "diffing is slow unoptimized (recommended by insta)",
+    },
+];
+
+pub fn run(apply: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut doc = manifest::read_manifest()?;
+
+    // Transitive crates matter too, so prefer the lockfile when there is one
+    let used: Vec<String> = match lockfile::read_lockfile(Path::new(LOCKFILE_PATH)) {
+        Ok(packages) => packages.into_iter().map(|package| package.name).collect(),
+        Err(_) => manifest::direct_dependencies(&doc),
+    };
+
+    let missing: Vec<_> = RECOMMENDED_OVERRIDES
+        .iter()
+        .filter(|o| used.iter().any(|used| used == o.package))
+        .filter(|o| current_override(&doc, o.package).is_none_or(|current| current < LEVEL))
+        .collect();
+
+    if missing.is_empty() {
+        println!("✓ No recommended profile overrides are missing.");
+        return Ok(());
+    }
+
+    println!("Recommended profile overrides:");
+    for o in &missing {
+        println!(
+            "  - [profile.{}.package.{}] {} = {} ({})",
+            PROFILE, o.package, KEY, LEVEL, o.reason
+        );
+    }
+
+    if !apply {
+        println!("\nRun with --apply-recommended-overrides to add them to Cargo.toml.");
+        return Ok(());
+    }
+
+    let mut added = 0;
+    for o in &missing {
+        if set_override(&mut doc, o.package) {
+            added += 1;
+        } else {
+            println!(
+                "✗ Skipped {}: an entry on the path to [profile.{}.package.{}] isn't a table",
+                o.package, PROFILE, o.package
+            );
+        }
+    }
+    if added > 0 {
+        manifest::write_manifest(&doc)?;
+    }
+    println!(
+        "\n✓ Added {} of {} overrides to Cargo.toml",
+        added,
+        missing.len()
+    );
+
+    Ok(())
+}
+
+/// The override value in effect for `name`, honouring `[profile.<p>.package."*"]`.
+fn current_override(doc: &DocumentMut, name: &str) -> Option<i64> {
+    let packages = doc.get("profile")?.get(PROFILE)?.get("package")?;
+    [name, "*"]
+        .iter()
+        .filter_map(|package| packages.get(package)?.get(KEY)?.as_integer())
+        .max()
+}
+
+/// Sets the override, creating missing tables along the way and writing into
+/// inline tables such as `package = { sha2 = { ... } }` where they already
+/// exist. `false` when an entry on the path isn't a table.
+fn set_override(doc: &mut DocumentMut, package: &str) -> bool {
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    let mut inline = false;
+    for header in ["profile", PROFILE, "package", package] {
+        let entry = table.entry(header).or_insert_with(|| {
+            // An inline table can only hold more inline tables
+            if inline {
+                value(InlineTable::new())
+            } else {
+                let mut implicit = Table::new();
+                implicit.set_implicit(true);
+                Item::Table(implicit)
+            }
+        });
+        if header == package
+            && let Item::Table(target) = &mut *entry
+        {
+            target.set_implicit(false);
+        }
+        inline = entry.is_inline_table();
+        let Some(next) = entry.as_table_like_mut() else {
+            return false;
+        };
+        table = next;
+    }
+    table.insert(KEY, value(LEVEL));
+    true
+}
@@ -0,0 +1,119 @@
+use crate::commands::confirm;
+use crate::{manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, value};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let no_std = Regex::new(r"^\s*#!\[(no_std|cfg_attr\(.*\bno_std\s*\))\]")?;
+    let panic_handler = Regex::new(r"^\s*#\[panic_handler\]")?;
+
+    let mut no_std_roots = Vec::new();
+    let mut defines_handler = false;
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        if is_crate_root(&file) && content.lines().any(|line| no_std.is_match(line)) {
+            no_std_roots.push(file.clone());
+        }
+        defines_handler |= content.lines().any(|line| panic_handler.is_match(line));
+    }
+
+    if no_std_roots.is_empty() {
+        println!("No `#![no_std]` crate roots found; the std panic handler applies.");
+        return Ok(());
+    }
+
+    // Only the final binary links a panic handler; libraries leave it to the binary
+    let binaries: Vec<&PathBuf> = no_std_roots
+        .iter()
+        .filter(|file| !file.ends_with("lib.rs"))
+        .collect();
+    let mut doc = manifest::read_manifest()?;
+    let mut problems = 0;
+
+    if binaries.is_empty() {
+        println!(
+            "✓ Only the library is no_std; the binary that uses it provides the panic handler."
+        );
+    } else if defines_handler {
+        println!("✓ A #[panic_handler] function is defined in src/");
+    } else if let Some(handler) = handler_crate(&doc) {
+        println!("✓ {} provides the panic handler", handler);
+    } else {
+        problems += 1;
+        for file in &binaries {
+            println!("✗ {} is no_std", file.display());
+        }
+        println!(
+            "✗ no_std project detected but no panic handler found in dependencies. \
+             Add `panic-halt` or similar to [dependencies]."
+        );
+    }
+
+    let release_panic = doc
+        .get("profile")
+        .and_then(|profile| profile.get("release"))
+        .and_then(|release| release.get("panic"))
+        .and_then(Item::as_str)
+        .map(str::to_string);
+    match release_panic.as_deref() {
+        Some("abort") => println!("✓ [profile.release] sets panic = \"abort\""),
+        other => {
+            problems += 1;
+            match other {
+                Some(strategy) => println!(
+                    "✗ [profile.release] sets panic = \"{}\"; no_std targets have no unwinder, use \"abort\"",
+                    strategy
+                ),
+                None => println!(
+                    "✗ [profile.release] doesn't set panic = \"abort\"; no_std targets have no unwinder"
+                ),
+            }
+            if confirm("\nSet panic = \"abort\" in [profile.release]?") {
+                set_release_abort(&mut doc)?;
+                manifest::write_manifest(&doc)?;
+                problems -= 1;
+                println!("✓ Set panic = \"abort\" in [profile.release]");
+            }
+        }
+    }
+
+    if problems == 0 {
+        println!("\n✓ Panic handling is configured for no_std.");
+    }
+    Ok(())
+}
+
+/// lib.rs, main.rs and src/bin/ targets, where crate attributes live.
+fn is_crate_root(file: &Path) -> bool {
+    file.ends_with("lib.rs")
+        || file.ends_with("main.rs")
+        || file.parent().is_some_and(|dir| dir.ends_with("bin"))
+}
+
+/// A `panic-*` dependency such as panic-halt, panic-probe or panic-semihosting.
+fn handler_crate(doc: &DocumentMut) -> Option<String> {
+    manifest::direct_dependencies(doc)
+        .into_iter()
+        .find(|name| name.starts_with("panic-"))
+}
+
+fn set_release_abort(doc: &mut DocumentMut) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = doc
+        .entry("profile")
+        .or_insert_with(|| {
+            let mut implicit = Table::new();
+            implicit.set_implicit(true);
+            Item::Table(implicit)
+        })
+        .as_table_like_mut()
+        .ok_or("[profile] is not a table")?;
+    profile
+        .entry("release")
+        .or_insert(Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or("[profile.release] is not a table")?
+        .insert("panic", value("abort"));
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::{cratesio, manifest};
+use toml_edit::Item;
+
+/// Well-known platform-specific crates, used before asking crates.io.
+const KNOWN_PLATFORMS: &[(&str, &[&str])] = &[
+    ("winapi", &["windows"]),
+    ("windows", &["windows"]),
+    ("windows-sys", &["windows"]),
+    ("winreg", &["windows"]),
+    ("nix", &["unix"]),
+    ("inotify", &["linux"]),
+    ("io-uring", &["linux"]),
+    ("tokio-uring", &["linux"]),
+    ("procfs", &["linux"]),
+    ("core-foundation", &["macos"]),
+    ("cocoa", &["macos"]),
+    ("objc", &["macos"]),
+    ("fsevent-sys", &["macos"]),
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let Some(targets) = doc.get("target").and_then(Item::as_table_like) else {
+        println!("No target-specific dependency sections found.");
+        return Ok(());
+    };
+
+    let mut problems = 0;
+    for (cfg, target) in targets.iter() {
+        let Some(platform) = section_platform(cfg) else {
+            println!(
+                "Skipping [target.'{}'] (condition too complex to check)",
+                cfg
+            );
+            continue;
+        };
+
+        for kind in manifest::DEPENDENCY_KINDS {
+            let Some(table) = target.get(kind).and_then(Item::as_table_like) else {
+                continue;
+            };
+            for (key, item) in table.iter() {
+                let package = manifest::package_name(key, item);
+                let supported = supported_platforms(&package);
+                if supported.is_empty() || compatible(platform, &supported) {
+                    continue;
+                }
+
+                problems += 1;
+                println!(
+                    "✗ {} in [target.'{}'.{}] only supports {}",
+                    package,
+                    cfg,
+                    kind,
+                    supported.join(", ")
+                );
+            }
+        }
+    }
+
+    if problems == 0 {
+        println!("✓ Target-specific dependencies match their platforms.");
+    }
+    Ok(())
+}
+
+/// The platform a `[target.<cfg>]` key selects, for the simple cfgs people use.
+fn section_platform(cfg: &str) -> Option<&'static str> {
+    let cfg = cfg.replace(' ', "");
+    if cfg.contains("not(") || cfg.contains("any(") || cfg.contains("all(") {
+        return None;
+    }
+
+    if cfg == "cfg(windows)" || cfg.contains("\"windows\"") || cfg.contains("-windows-") {
+        Some("windows")
+    } else if cfg.contains("\"linux\"") || cfg.contains("-linux-") {
+        Some("linux")
+    } else if cfg.contains("\"macos\"") || cfg.contains("-apple-darwin") {
+        Some("macos")
+    } else if cfg == "cfg(unix)" || cfg.contains("\"unix\"") {
+        Some("unix")
+    } else {
+        None
+    }
+}
+
+/// Platforms a crate declares support for. Empty means it's treated as portable.
+fn supported_platforms(package: &str) -> Vec<String> {
+    if let Some((_, platforms)) = KNOWN_PLATFORMS.iter().find(|(name, _)| *name == package) {
+        return platforms.iter().map(|p| p.to_string()).collect();
+    }
+
+    // crates.io has `os::<platform>-apis` categories for OS-specific crates
+    let Ok(info) = cratesio::crate_info(package) else {
+        return Vec::new();
+    };
+    info["crate"]["categories"]
+        .as_array()
+        .map(|categories| {
+            categories
+                .iter()
+                .filter_map(|c| c.as_str())
+                .filter_map(|c| c.strip_prefix("os::")?.strip_suffix("-apis"))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn compatible(platform: &str, supported: &[String]) -> bool {
+    let supports = |p: &str| supported.iter().any(|s| s == p);
+    match platform {
+        "windows" => supports("windows"),
+        "linux" | "macos" => supports(platform) || supports("unix"),
+        "unix" => supports("unix") || (supports("linux") && supports("macos")),
+        _ => true,
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{metadata, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Source patterns that usually point to a hygiene mistake, checked in order;
+/// only the first match on a line is reported.
+const ANTI_PATTERNS: &[(&str, &str)] = &[
+    (
+        r"Ident::new\([^;]*def_site\(\)",
+        "identifier created with def_site() is invisible to the macro caller; use Span::call_site() if callers must name it",
+    ),
+    (
+        r"\.def_site\(\)",
+        "def_site() is an associated function; calling it on a span value discards that span's hygiene",
+    ),
+    (
+        r"Span::def_site\(\)",
+        "Span::def_site() is unstable and its hygiene differs from macro_rules!; prefer Span::mixed_site() on stable",
+    ),
+    (
+        r#"Ident::new\(\s*(?:&?format!\(\s*)?"__[^;]*call_site\(\)"#,
+        "hidden helper identifier uses the caller's hygiene; give it Span::mixed_site() so it can't clash with user code",
+    ),
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+
+    let mut roots = Vec::new();
+    for package in metadata::workspace_packages(&metadata) {
+        for target in package["targets"].as_array().into_iter().flatten() {
+            let is_proc_macro = target["kind"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|k| k == "proc-macro"));
+            if is_proc_macro
+                && let Some(src_path) = target["src_path"].as_str()
+                && let Some(dir) = Path::new(src_path).parent()
+            {
+                roots.push((
+                    package["name"].as_str().unwrap_or("?").to_string(),
+                    dir.to_path_buf(),
+                ));
+            }
+        }
+    }
+
+    if roots.is_empty() {
+        println!("No proc-macro crates found in the workspace.");
+        return Ok(());
+    }
+
+    let patterns: Vec<(Regex, &str)> = ANTI_PATTERNS
+        .iter()
+        .map(|(pattern, message)| Ok((Regex::new(pattern)?, *message)))
+        .collect::<Result<_, regex::Error>>()?;
+
+    let mut warnings = 0;
+    for (name, root) in &roots {
+        println!("Checking proc-macro crate {}...", name);
+        for file in source::rust_files(root) {
+            let content = fs::read_to_string(&file)?;
+            for (index, line) in content.lines().enumerate() {
+                if line.trim_start().starts_with("//") {
+                    continue;
+                }
+                if let Some((_, message)) = patterns.iter().find(|(regex, _)| regex.is_match(line))
+                {
+                    warnings += 1;
+                    println!("  ✗ {}:{}: {}", file.display(), index + 1, message);
+                }
+            }
+        }
+    }
+
+    if warnings == 0 {
+        println!("\n✓ No hygiene anti-patterns found.");
+    } else {
+        println!(
+            "\n{} possible hygiene issue(s). These are heuristics; review each one.",
+            warnings
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use crate::manifest;
+use toml_edit::{DocumentMut, Item};
+
+/// `[package]` fields crates.io needs or strongly expects. Alternatives are
+/// separated by `|`.
+const REQUIRED_FIELDS: &[&str] = &[
+    "name",
+    "version",
+    "edition",
+    "description",
+    "license|license-file",
+    "repository",
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let Some(package) = doc.get("package").and_then(Item::as_table_like) else {
+        return Err("Cargo.toml has no [package] section; run this in a crate directory".into());
+    };
+
+    let mut passed = 0;
+    let mut total = 0;
+    let mut check = |ok: bool, message: String| {
+        total += 1;
+        if ok {
+            passed += 1;
+            println!("✓ {}", message);
+        } else {
+            println!("✗ {}", message);
+        }
+    };
+
+    for field in REQUIRED_FIELDS {
+        let alternatives: Vec<&str> = field.split('|').collect();
+        let present = alternatives
+            .iter()
+            .any(|key| package.get(key).is_some_and(is_set));
+        let label = alternatives.join(" or ");
+        check(
+            present,
+            format!(
+                "[package] {} {}",
+                if present { "has" } else { "is missing" },
+                label
+            ),
+        );
+    }
+
+    let publish = package.get("publish");
+    let blocked = publish.and_then(Item::as_bool) == Some(false)
+        || publish
+            .and_then(Item::as_array)
+            .is_some_and(|registries| registries.is_empty());
+    let allows_crates_io = publish
+        .and_then(Item::as_array)
+        .is_none_or(|registries| registries.iter().any(|r| r.as_str() == Some("crates-io")));
+    if blocked {
+        check(false, "publish = false prevents publishing".to_string());
+    } else if !allows_crates_io {
+        check(
+            false,
+            "publish only lists other registries, not crates-io".to_string(),
+        );
+    } else {
+        check(true, "publishing is allowed".to_string());
+    }
+
+    let path_deps = dependencies_with(&doc, "path");
+    check(
+        path_deps.is_empty(),
+        if path_deps.is_empty() {
+            "no path dependencies without a version".to_string()
+        } else {
+            format!(
+                "path dependencies without a version can't be published: {}",
+                path_deps.join(", ")
+            )
+        },
+    );
+
+    let git_deps = dependencies_with(&doc, "git");
+    check(
+        git_deps.is_empty(),
+        if git_deps.is_empty() {
+            "no git dependencies without a version".to_string()
+        } else {
+            format!(
+                "git dependencies without a version can't be published: {}",
+                git_deps.join(", ")
+            )
+        },
+    );
+
+    let score = passed * 100 / total;
+    println!(
+        "\nPublish readiness: {}/{} checks passed ({}%)",
+        passed, total, score
+    );
+    if passed == total {
+        println!("✓ Ready for `cargo publish`.");
+        return Ok(());
+    }
+
+    Err(format!("{} publish check(s) failed", total - passed).into())
+}
+
+/// A field counts as set if it's non-empty or inherited with `workspace = true`.
+fn is_set(item: &Item) -> bool {
+    match item.as_str() {
+        Some(value) => !value.trim().is_empty(),
+        None => item
+            .get("workspace")
+            .and_then(Item::as_bool)
+            .unwrap_or(false),
+    }
+}
+
+/// Dependencies outside `[dev-dependencies]` that use `source` (`path` or
+/// `git`) without a `version` for crates.io to fall back to. Dev-dependencies
+/// without a version are dropped on publish, so they're fine.
+fn dependencies_with(doc: &DocumentMut, source: &str) -> Vec<String> {
+    let mut names: Vec<String> = manifest::dependency_tables(doc)
+        .into_iter()
+        .filter(|(section, _)| !section.ends_with("dev-dependencies"))
+        .flat_map(|(section, table)| {
+            table
+                .iter()
+                .filter(|(_, item)| {
+                    item.as_table_like().is_some_and(|spec| {
+                        spec.contains_key(source) && !spec.contains_key("version")
+                    })
+                })
+                .map(|(key, _)| format!("{} ([{}])", key, section))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    names.sort();
+    names
+}
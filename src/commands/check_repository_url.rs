@@ -0,0 +1,85 @@
+use crate::{cratesio, manifest};
+use regex::Regex;
+use toml_edit::Item;
+
+pub fn run(check_exists: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let package = doc
+        .get("package")
+        .and_then(Item::as_table_like)
+        .ok_or("Cargo.toml has no [package] section")?;
+
+    let Some(repository) = package.get("repository") else {
+        return Err("[package] has no repository field".into());
+    };
+    // `repository.workspace = true` takes the value from [workspace.package]
+    let url = match repository.as_str() {
+        Some(url) => url.to_string(),
+        None => doc
+            .get("workspace")
+            .and_then(|workspace| workspace.get("package"))
+            .and_then(|package| package.get("repository"))
+            .and_then(Item::as_str)
+            .map(str::to_string)
+            .ok_or("repository is inherited from a workspace root that isn't in this Cargo.toml")?,
+    };
+    println!("repository = \"{}\"\n", url);
+
+    let ssh_regex = Regex::new(r"^(?:ssh://)?git@([^:/]+)[:/](.+?)(?:\.git)?/?$")?;
+    let url_regex = Regex::new(r"^(https?)://([A-Za-z0-9.-]+)(:\d+)?(/\S*)?$")?;
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    if let Some(cap) = ssh_regex.captures(&url) {
+        warnings += 1;
+        println!(
+            "Warning: SSH URLs work for cloning but aren't links crates.io and docs.rs can show; use https://{}/{}",
+            &cap[1], &cap[2]
+        );
+    } else if let Some(cap) = url_regex.captures(&url) {
+        let host = cap[2].to_lowercase();
+        if &cap[1] == "http" {
+            errors += 1;
+            println!("✗ Use https:// instead of http://");
+        }
+        if host == "github.com" && cap.get(4).is_none_or(|path| path.as_str().len() <= 1) {
+            errors += 1;
+            println!(
+                "✗ GitHub URLs should point at a repository: https://github.com/<owner>/<repo>"
+            );
+        }
+        if url.ends_with('/') {
+            errors += 1;
+            println!("✗ Remove the trailing slash: {}", url.trim_end_matches('/'));
+        }
+
+        if errors == 0 && check_exists {
+            match cratesio::head_status(&url) {
+                Ok(status) if status < 400 => println!("✓ The repository exists (HTTP {})", status),
+                Ok(status) => {
+                    errors += 1;
+                    println!("✗ The repository URL returned HTTP {}", status);
+                }
+                Err(e) => {
+                    warnings += 1;
+                    println!("Warning: could not reach the repository: {}", e);
+                }
+            }
+        }
+    } else {
+        errors += 1;
+        println!("✗ Not a valid URL; expected https://<host>/<path>");
+    }
+
+    if errors == 0 {
+        if warnings == 0 {
+            println!("✓ The repository URL is well-formed.");
+            if !check_exists {
+                println!("Run with --check-exists to also check that it's reachable.");
+            }
+        }
+        return Ok(());
+    }
+
+    Err(format!("{} problem(s) with the repository URL", errors).into())
+}
@@ -0,0 +1,107 @@
+use crate::commands::backticked;
+use crate::{extract_missing_crates, is_std_module, manifest};
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::process::Command;
+use toml_edit::value;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let v1 = missing_with_resolver("1")?;
+    let v2 = missing_with_resolver("2")?;
+
+    let only_v1: Vec<String> = v1.iter().filter(|c| !v2.contains(c)).cloned().collect();
+    let only_v2: Vec<String> = v2.iter().filter(|c| !v1.contains(c)).cloned().collect();
+
+    println!();
+    if only_v1.is_empty() && only_v2.is_empty() {
+        if v1.is_empty() {
+            println!("✓ No missing crates or items under either resolver.");
+        } else {
+            println!(
+                "Both resolvers report the same missing crates and items: {}",
+                backticked(&v1)
+            );
+        }
+        return Ok(());
+    }
+
+    if !only_v1.is_empty() {
+        println!(
+            "Missing only with resolver = \"1\": {}",
+            backticked(&only_v1)
+        );
+    }
+    if !only_v2.is_empty() {
+        println!(
+            "Missing only with resolver = \"2\": {}",
+            backticked(&only_v2)
+        );
+        println!("Resolver 2 no longer unifies features between normal, dev and build");
+        println!("dependencies, so a feature one of them relied on may need enabling explicitly.");
+    }
+
+    Ok(())
+}
+
+/// Temporarily sets the resolver in Cargo.toml and collects missing crates
+/// and unresolved paths from `cargo check --all-targets`.
+fn missing_with_resolver(resolver: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    println!("Checking with resolver = \"{}\"...", resolver);
+
+    manifest::with_restored_manifest(|| {
+        let mut doc = manifest::read_manifest()?;
+        // A workspace root sets the resolver for every member
+        let section = if doc.contains_key("workspace") {
+            "workspace"
+        } else {
+            "package"
+        };
+        doc[section]["resolver"] = value(resolver);
+        manifest::write_manifest(&doc)?;
+
+        let output = Command::new("cargo")
+            .args(["check", "--all-targets"])
+            .output()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("failed to parse manifest") {
+            return Err(format!(
+                "cargo rejected resolver = \"{}\": {}",
+                resolver,
+                stderr.trim()
+            )
+            .into());
+        }
+        let mut missing = extract_missing_crates(&stderr);
+        missing.extend(unresolved_paths(&stderr));
+        Ok(missing)
+    })
+}
+
+/// Paths into a dependency that didn't resolve, such as `tokio::net` from
+/// "unresolved import `tokio::net`". A crate that's present but built without
+/// a feature fails this way, which is how feature unification differences
+/// between the resolvers usually show up.
+fn unresolved_paths(stderr: &str) -> BTreeSet<String> {
+    let import_regex = Regex::new(r"unresolved imports? ((?:`[^`]+::[^`]+`(?:, )?)+)").unwrap();
+    let path_regex = Regex::new(r"`([^`]+::[^`]+)`").unwrap();
+    let find_regex = Regex::new(r"could not find `([^`]+)` in `([^`]+)`").unwrap();
+
+    let mut paths = BTreeSet::new();
+    for cap in import_regex.captures_iter(stderr) {
+        paths.extend(
+            path_regex
+                .captures_iter(&cap[1])
+                .map(|path| path[1].to_string()),
+        );
+    }
+    for cap in find_regex.captures_iter(stderr) {
+        paths.insert(format!("{}::{}", &cap[2], &cap[1]));
+    }
+    paths
+        .into_iter()
+        .filter(|path| {
+            let root = path.split("::").next().unwrap_or_default();
+            !is_std_module(root) && !["crate", "self", "super"].contains(&root)
+        })
+        .collect()
+}
@@ -0,0 +1,104 @@
+use crate::manifest;
+use std::fs;
+use toml_edit::Item;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+
+    let mut runtime = Vec::new();
+    let mut build = Vec::new();
+    for (section, table) in manifest::dependency_tables(&doc) {
+        let names = table
+            .iter()
+            .map(|(key, item)| manifest::package_name(key, item));
+        if section.ends_with("build-dependencies") {
+            build.extend(names);
+        } else {
+            runtime.extend(names);
+        }
+    }
+    let has = |names: &[String], name: &str| names.iter().any(|n| n == name);
+
+    let uses_tonic = has(&runtime, "tonic");
+    let uses_prost = has(&runtime, "prost");
+    if !uses_tonic && !uses_prost {
+        println!("Neither tonic nor prost is a dependency.");
+        return Ok(());
+    }
+
+    let mut problems = 0;
+    // tonic >= 0.14 moved the prost codegen into tonic-prost-build
+    let generator = if uses_tonic {
+        ["tonic-build", "tonic-prost-build"]
+            .into_iter()
+            .find(|name| has(&build, name))
+    } else {
+        Some("prost-build").filter(|name| has(&build, name))
+    };
+    let expected = if uses_tonic {
+        "tonic-build"
+    } else {
+        "prost-build"
+    };
+
+    match generator {
+        Some(name) => println!("✓ {} is in [build-dependencies]", name),
+        None => {
+            problems += 1;
+            println!(
+                "✗ {} needs {} in [build-dependencies] to generate code from .proto files",
+                if uses_tonic { "tonic" } else { "prost" },
+                expected
+            );
+        }
+    }
+
+    if uses_tonic && !uses_prost {
+        problems += 1;
+        println!("✗ tonic's generated code uses prost; add prost to [dependencies]");
+    }
+
+    let build_script = doc
+        .get("package")
+        .and_then(|package| package.get("build"))
+        .and_then(Item::as_str)
+        .unwrap_or("build.rs");
+    let module = generator.unwrap_or(expected).replace('-', "_");
+    match fs::read_to_string(build_script) {
+        Err(_) => {
+            problems += 1;
+            println!("✗ No {} to compile the .proto files", build_script);
+        }
+        Ok(content) if !calls_codegen(&content) => {
+            problems += 1;
+            println!(
+                "✗ {} doesn't call {}::compile_protos or configure()",
+                build_script, module
+            );
+        }
+        Ok(_) => println!("✓ {} compiles the .proto files", build_script),
+    }
+
+    if problems == 0 {
+        println!("\n✓ gRPC/protobuf setup is complete.");
+        return Ok(());
+    }
+
+    println!("\nExample {}:\n", build_script);
+    println!("fn main() -> Result<(), Box<dyn std::error::Error>> {{");
+    println!("    {}::compile_protos(\"proto/service.proto\")?;", module);
+    println!("    Ok(())");
+    println!("}}");
+    Ok(())
+}
+
+/// Whether a build script runs one of the tonic/prost code generators.
+fn calls_codegen(content: &str) -> bool {
+    ["tonic_build", "tonic_prost_build", "prost_build"]
+        .iter()
+        .any(|module| {
+            content.contains(&format!("{}::compile_protos", module))
+                || content.contains(&format!("{}::configure", module))
+                || content.contains(&format!("{}::Config", module))
+        })
+}
@@ -0,0 +1,150 @@
+use crate::commands::{cargo_home, confirm};
+use crate::{cratesio, manifest};
+use regex::Regex;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+struct Upgrade {
+    name: String,
+    from: String,
+    to: String,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    if !semver_checks_installed() {
+        println!("cargo-semver-checks is not installed.");
+        println!("Install it with `cargo install cargo-semver-checks` and re-run this command.");
+        return Ok(());
+    }
+
+    let mut doc = manifest::read_manifest()?;
+    let direct = manifest::direct_dependencies(&doc);
+
+    let upgrades: Vec<Upgrade> = pending_upgrades()?
+        .into_iter()
+        .filter(|upgrade| direct.contains(&upgrade.name))
+        .collect();
+
+    if upgrades.is_empty() {
+        println!("No pending upgrades for direct dependencies.");
+        return Ok(());
+    }
+
+    let mut breaking = Vec::new();
+    for upgrade in &upgrades {
+        println!(
+            "Checking {} {} -> {}...",
+            upgrade.name, upgrade.from, upgrade.to
+        );
+
+        match check_upgrade(upgrade) {
+            Ok(None) => println!("✓ No breaking changes in {} {}", upgrade.name, upgrade.to),
+            Ok(Some(report)) => {
+                println!("✗ Breaking changes in {} {}:", upgrade.name, upgrade.to);
+                println!("{}", report.trim());
+                breaking.push(upgrade);
+            }
+            Err(e) => println!("✗ Could not check {}: {}", upgrade.name, e),
+        }
+    }
+
+    if breaking.is_empty() {
+        println!("\nAll pending upgrades are SemVer compatible.");
+        return Ok(());
+    }
+
+    println!();
+    let mut pinned = false;
+    for upgrade in breaking {
+        let prompt = format!("Pin {} to ={} in Cargo.toml?", upgrade.name, upgrade.from);
+        if confirm(&prompt) {
+            let req = format!("={}", upgrade.from);
+            if manifest::set_dependency_version(&mut doc, &upgrade.name, &req) {
+                pinned = true;
+                println!("✓ Pinned {} to {}", upgrade.name, req);
+            } else {
+                println!(
+                    "✗ Skipped {}: it isn't declared in Cargo.toml, so there's nothing to pin",
+                    upgrade.name
+                );
+            }
+        }
+    }
+
+    if pinned {
+        manifest::write_manifest(&doc)?;
+    }
+
+    Ok(())
+}
+
+fn semver_checks_installed() -> bool {
+    Command::new("cargo")
+        .args(["semver-checks", "--version"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Upgrades that `cargo update` would apply, without touching Cargo.lock.
+fn pending_upgrades() -> Result<Vec<Upgrade>, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args(["update", "--dry-run"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let update_regex = Regex::new(r"Updating (\S+) v(\S+) -> v(\S+)")?;
+    let upgrades = update_regex
+        .captures_iter(&stderr)
+        .map(|cap| Upgrade {
+            name: cap[1].to_string(),
+            from: cap[2].to_string(),
+            to: cap[3].to_string(),
+        })
+        .collect();
+
+    Ok(upgrades)
+}
+
+/// Runs cargo-semver-checks with the locked version as baseline. Returns the
+/// report when breaking changes were found.
+fn check_upgrade(upgrade: &Upgrade) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let baseline = crate_source(&upgrade.name, &upgrade.from)?;
+    let current = crate_source(&upgrade.name, &upgrade.to)?;
+
+    let output = Command::new("cargo")
+        .arg("semver-checks")
+        .arg("--manifest-path")
+        .arg(current.join("Cargo.toml"))
+        .arg("--baseline-root")
+        .arg(&baseline)
+        .output()?;
+
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let report = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(Some(report))
+}
+
+/// Prefers the sources cargo has already unpacked in the registry cache and
+/// only downloads the crate when it isn't there.
+fn crate_source(name: &str, version: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let registry_src = cargo_home().join("registry").join("src");
+    if let Ok(indexes) = fs::read_dir(&registry_src) {
+        for index in indexes.flatten() {
+            let candidate = index.path().join(format!("{}-{}", name, version));
+            if candidate.join("Cargo.toml").exists() {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    cratesio::download_crate(name, version)
+}
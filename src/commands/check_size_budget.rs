@@ -0,0 +1,113 @@
+use crate::commands::format_size;
+use crate::config::{self, CONFIG_PATH};
+use crate::manifest;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::Command;
+
+pub fn run(add: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = config::read_config()?;
+    let budget_kb = config
+        .get("size-budget")
+        .and_then(|budget| budget.get("release_binary_kb"))
+        .and_then(|kb| kb.as_integer())
+        .map(|kb| kb as u64);
+
+    if budget_kb.is_none() && add.is_none() {
+        return Err(format!(
+            "no budget configured; add `[size-budget] release_binary_kb = <n>` to {}",
+            CONFIG_PATH
+        )
+        .into());
+    }
+
+    println!("Building release binaries...");
+    let mut sizes = release_binary_sizes()?;
+
+    if let Some(crate_name) = &add {
+        println!("Building release binaries with {} added...", crate_name);
+        let with_crate = manifest::with_restored_manifest(|| {
+            let output = Command::new("cargo").args(["add", crate_name]).output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("cargo add {} failed: {}", crate_name, stderr.trim()).into());
+            }
+            release_binary_sizes()
+        })?;
+
+        println!("\nEstimated impact of adding {}:", crate_name);
+        for (binary, after) in &with_crate {
+            let before = sizes.get(binary).copied().unwrap_or(0);
+            let delta = *after as i64 - before as i64;
+            let sign = if delta < 0 { "-" } else { "+" };
+            println!(
+                "  - {}: {} -> {} ({}{})",
+                binary,
+                format_size(before),
+                format_size(*after),
+                sign,
+                format_size(delta.unsigned_abs())
+            );
+        }
+        println!("Note: code the project doesn't call yet is stripped by the linker.");
+        sizes = with_crate;
+    }
+
+    let Some(budget_kb) = budget_kb else {
+        return Ok(());
+    };
+
+    println!(
+        "\nRelease binary size budget: {}",
+        format_size(budget_kb * 1024)
+    );
+    let mut over = 0;
+    for (binary, size) in &sizes {
+        if *size > budget_kb * 1024 {
+            over += 1;
+            println!("✗ {} is {} (over budget)", binary, format_size(*size));
+        } else {
+            println!("✓ {} is {}", binary, format_size(*size));
+        }
+    }
+
+    if over > 0 {
+        return Err(format!("{} binaries exceed the size budget", over).into());
+    }
+    Ok(())
+}
+
+/// Builds in release mode and returns the size of every produced executable.
+fn release_binary_sizes() -> Result<BTreeMap<String, u64>, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args([
+            "build",
+            "--release",
+            "--message-format=json-render-diagnostics",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("release build failed: {}", stderr.trim()).into());
+    }
+
+    let mut sizes = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-artifact" {
+            continue;
+        }
+        if let (Some(name), Some(executable)) = (
+            message["target"]["name"].as_str(),
+            message["executable"].as_str(),
+        ) {
+            sizes.insert(name.to_string(), fs::metadata(executable)?.len());
+        }
+    }
+
+    Ok(sizes)
+}
@@ -0,0 +1,51 @@
+use crate::source;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Scanning src/ for std usage in a no_std context...\n");
+
+    let use_std = Regex::new(r"^\s*(pub(\([^)]*\))?\s+)?use\s+(::)?std\b")?;
+    let extern_std = Regex::new(r"^\s*extern\s+crate\s+std\b")?;
+    let no_std = Regex::new(r"^\s*#!\[no_std\]")?;
+
+    let mut violations = 0;
+    let mut declares_no_std = false;
+
+    for file in source::rust_files(Path::new("src")) {
+        let content = fs::read_to_string(&file)?;
+        for (index, line) in content.lines().enumerate() {
+            if no_std.is_match(line) {
+                declares_no_std = true;
+            } else if extern_std.is_match(line) {
+                violations += 1;
+                println!(
+                    "✗ {}:{}: `extern crate std;` reintroduces std",
+                    file.display(),
+                    index + 1
+                );
+            } else if use_std.is_match(line) {
+                violations += 1;
+                println!(
+                    "✗ {}:{}: {} (use core:: or alloc:: instead)",
+                    file.display(),
+                    index + 1,
+                    line.trim()
+                );
+            }
+        }
+    }
+
+    if !declares_no_std {
+        println!("Note: no `#![no_std]` attribute found; results assume a no_std target.");
+    }
+
+    if violations == 0 {
+        println!("✓ No std usage found.");
+    } else {
+        println!("\n{} no_std violation(s) found.", violations);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::{manifest, source};
+use std::fs;
+use std::path::Path;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let dependencies = manifest::direct_dependencies(&doc);
+    let has_tokio = dependencies.iter().any(|d| d == "tokio");
+    let async_test = if has_tokio {
+        "#[tokio::test]"
+    } else if dependencies.iter().any(|d| d == "async-std") {
+        "#[async_std::test]"
+    } else {
+        "an async test attribute such as #[tokio::test]"
+    };
+
+    let mut problems = Vec::new();
+
+    for file in source::rust_files(Path::new("tests")) {
+        let content = fs::read_to_string(&file)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let path = file.display().to_string();
+
+        // tests/common/mod.rs and friends are compiled into every test binary,
+        // and each binary only uses some of the helpers
+        if file.ends_with("mod.rs") && !content.contains("#![allow(dead_code)]") {
+            problems.push(format!(
+                "{}: shared test helpers should start with #![allow(dead_code)]",
+                path
+            ));
+        }
+
+        // Each file in tests/ is its own crate root
+        if (content.contains("extern crate test;") || content.contains("#[bench]"))
+            && !content.contains("#![feature(test)]")
+        {
+            problems.push(format!(
+                "{}: the `test` crate and #[bench] need #![feature(test)] (nightly only)",
+                path
+            ));
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            let line = line.trim();
+            if line == "#[test]"
+                && let Some(function) = next_item(&lines[index + 1..])
+                && function.contains("async fn")
+            {
+                problems.push(format!(
+                    "{}:{}: async test uses #[test]; use {} instead",
+                    path,
+                    index + 1,
+                    async_test
+                ));
+            }
+            if line.starts_with("#[tokio::test") && !has_tokio {
+                problems.push(format!(
+                    "{}:{}: #[tokio::test] but tokio isn't a dependency; add it to [dev-dependencies] with the `macros` and `rt` features",
+                    path,
+                    index + 1
+                ));
+            }
+        }
+    }
+
+    for file in source::rust_files(Path::new("src")) {
+        // `mod tests;` files are usually declared under #[cfg(test)] by their parent
+        if file.ends_with("tests.rs") || file.components().any(|c| c.as_os_str() == "tests") {
+            continue;
+        }
+        let content = fs::read_to_string(&file)?;
+        let mut in_cfg_test = false;
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with("#[cfg(test)]") || line.starts_with("#![cfg(test)]") {
+                in_cfg_test = true;
+            }
+            if line == "#[test]" && !in_cfg_test {
+                problems.push(format!(
+                    "{}:{}: #[test] outside a #[cfg(test)] module; move it into one or into tests/",
+                    file.display(),
+                    index + 1
+                ));
+            }
+        }
+    }
+
+    let test_sources = source::rust_files(Path::new("tests"))
+        .into_iter()
+        .chain(source::rust_files(Path::new("src")));
+    for file in test_sources {
+        let content = fs::read_to_string(&file)?;
+        let lines: Vec<&str> = content.lines().collect();
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim() != "#[ignore]" || explains_ignore(&lines, index) {
+                continue;
+            }
+            problems.push(format!(
+                "{}:{}: #[ignore] without a reason; use #[ignore = \"...\"] or add a comment",
+                file.display(),
+                index + 1
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        println!("✓ Test attributes look correct.");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("✗ {}", problem);
+    }
+    println!("\n{} test attribute problem(s) found.", problems.len());
+    Ok(())
+}
+
+/// The first line after a run of attributes, comments and blank lines.
+fn next_item<'a>(lines: &[&'a str]) -> Option<&'a str> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))
+}
+
+/// Whether a bare `#[ignore]` has a comment directly above its attributes.
+/// A trailing comment on the same line already fails the bare match.
+fn explains_ignore(lines: &[&str], index: usize) -> bool {
+    lines[..index]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("//"))
+        .any(|line| line.starts_with("//"))
+}
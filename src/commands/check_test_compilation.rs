@@ -0,0 +1,34 @@
+use crate::{extract_missing_crates, install_dev_crates};
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Compiling tests without running them...\n");
+
+    let test_output = Command::new("cargo").args(["test", "--no-run"]).output()?;
+    let test_missing = extract_missing_crates(&String::from_utf8_lossy(&test_output.stderr));
+
+    // Crates missing from a plain `cargo check` are regular dependencies, not
+    // dev-dependencies, so they're left to the main analysis.
+    let check_output = Command::new("cargo").arg("check").output()?;
+    let check_missing = extract_missing_crates(&String::from_utf8_lossy(&check_output.stderr));
+
+    let dev_missing: Vec<String> = test_missing
+        .into_iter()
+        .filter(|name| !check_missing.contains(name))
+        .collect();
+
+    if dev_missing.is_empty() {
+        println!("No test-only missing crates found!");
+        return Ok(());
+    }
+
+    println!("Crates missing only from test compilation:");
+    for crate_name in &dev_missing {
+        println!("  - {}", crate_name);
+    }
+
+    println!("\nAttempting to install as dev-dependencies...");
+    install_dev_crates(&dev_missing);
+
+    Ok(())
+}
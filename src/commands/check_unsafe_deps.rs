@@ -0,0 +1,134 @@
+use crate::config::{self, CONFIG_PATH};
+use crate::manifest;
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+use std::process::Command;
+use toml_edit::Item;
+
+const SAFETY_DOC: &str = "SAFETY.md";
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    if !geiger_installed() {
+        println!("cargo-geiger is not installed.");
+        println!("Install it with `cargo install cargo-geiger` and re-run this command.");
+        return Ok(());
+    }
+
+    let doc = manifest::read_manifest()?;
+    let direct: Vec<String> = doc
+        .get("dependencies")
+        .and_then(Item::as_table_like)
+        .map(|table| {
+            table
+                .iter()
+                .map(|(key, item)| manifest::package_name(key, item))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    println!("Running cargo geiger (this builds the project)...\n");
+    let unsafe_deps: Vec<String> = unsafe_packages()?
+        .into_iter()
+        .filter(|name| direct.contains(name))
+        .collect();
+    if unsafe_deps.is_empty() {
+        println!("✓ No crate in [dependencies] uses unsafe code.");
+        return Ok(());
+    }
+
+    let config = config::read_config()?;
+    let accepted = config
+        .get("safety")
+        .and_then(|safety| safety.get("accepted-unsafe"))
+        .and_then(Item::as_table_like);
+    let safety_doc = fs::read_to_string(SAFETY_DOC).unwrap_or_default();
+
+    let mut undocumented = Vec::new();
+    for name in &unsafe_deps {
+        if let Some(reason) = accepted
+            .and_then(|accepted| accepted.get(name))
+            .and_then(Item::as_str)
+            .filter(|reason| !reason.trim().is_empty())
+        {
+            println!("✓ {}: {}", name, reason);
+        } else if mentions(&safety_doc, name)? {
+            println!("✓ {}: documented in {}", name, SAFETY_DOC);
+        } else {
+            println!(
+                "✗ {} uses unsafe code and has no documented acceptance",
+                name
+            );
+            undocumented.push(name);
+        }
+    }
+
+    if undocumented.is_empty() {
+        println!("\n✓ Every unsafe dependency is documented.");
+        return Ok(());
+    }
+
+    println!(
+        "\nDocument why each one is accepted in {} (or in {}):\n",
+        CONFIG_PATH, SAFETY_DOC
+    );
+    println!("[safety.accepted-unsafe]");
+    for name in &undocumented {
+        println!(
+            "{} = \"<why this crate's unsafe code is acceptable>\"",
+            name
+        );
+    }
+
+    Err(format!(
+        "{} unsafe dependencies without a documented acceptance",
+        undocumented.len()
+    )
+    .into())
+}
+
+fn geiger_installed() -> bool {
+    Command::new("cargo")
+        .args(["geiger", "--version"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Packages whose compiled code contains unsafe functions, expressions,
+/// impls, traits or methods, according to `cargo geiger`.
+fn unsafe_packages() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args(["geiger", "--output-format", "Json"])
+        .output()?;
+    let report: Value = serde_json::from_slice(&output.stdout).map_err(|_| {
+        format!(
+            "cargo geiger failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+    })?;
+
+    let mut names: Vec<String> = report["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|package| {
+            package["unsafety"]["used"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .any(|(_, counts)| counts["unsafe_"].as_u64().unwrap_or(0) > 0)
+        })
+        .filter_map(|package| package["package"]["id"]["name"].as_str())
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Whether SAFETY.md names the crate as a whole word, e.g. in a heading or list.
+fn mentions(doc: &str, name: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let regex = Regex::new(&format!(r"(^|[^\w-]){}([^\w-]|$)", regex::escape(name)))?;
+    Ok(regex.is_match(doc))
+}
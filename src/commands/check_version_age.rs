@@ -0,0 +1,103 @@
+use crate::cratesio;
+use crate::lockfile::{self, LOCKFILE_PATH};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub fn run(max_age_days: i64) -> Result<(), Box<dyn std::error::Error>> {
+    let packages: Vec<_> = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?
+        .into_iter()
+        .filter(|package| package.is_from_crates_io())
+        .collect();
+
+    println!(
+        "Checking publish dates of {} locked crates (max age: {} days)...\n",
+        packages.len(),
+        max_age_days
+    );
+
+    let mut unmaintained = Vec::new();
+    let mut outdated = Vec::new();
+    // crate_info is throttled to one request per second, so a crate locked
+    // at several versions is only fetched once
+    let mut infos: HashMap<&str, Option<serde_json::Value>> = HashMap::new();
+
+    for package in &packages {
+        let info = infos.entry(&package.name).or_insert_with(|| {
+            cratesio::crate_info(&package.name)
+                .map_err(|e| println!("✗ Could not fetch {}: {}", package.name, e))
+                .ok()
+        });
+        let Some(info) = info else {
+            continue;
+        };
+        let versions = info["versions"].as_array().cloned().unwrap_or_default();
+        let age_of = |num: &str| {
+            versions
+                .iter()
+                .find(|v| v["num"] == num)
+                .and_then(|v| v["created_at"].as_str())
+                .and_then(cratesio::days_since)
+        };
+
+        let Some(locked_age) = age_of(&package.version) else {
+            continue;
+        };
+        if locked_age <= max_age_days {
+            continue;
+        }
+
+        let newest = info["crate"]["max_stable_version"]
+            .as_str()
+            .or_else(|| info["crate"]["max_version"].as_str())
+            .unwrap_or(&package.version)
+            .to_string();
+        let newest_age = age_of(&newest).unwrap_or(locked_age);
+
+        if newest_age > max_age_days {
+            unmaintained.push(if newest == package.version {
+                format!(
+                    "{} {} ({} days old, no newer release)",
+                    package.name, package.version, locked_age
+                )
+            } else {
+                format!(
+                    "{} {} ({} days old); newest {} is {} days old too",
+                    package.name, package.version, locked_age, newest, newest_age
+                )
+            });
+        } else {
+            outdated.push(format!(
+                "{} {} ({} days old) -> {} ({} days old)",
+                package.name, package.version, locked_age, newest, newest_age
+            ));
+        }
+    }
+
+    if unmaintained.is_empty() && outdated.is_empty() {
+        println!(
+            "✓ All locked versions were published within {} days.",
+            max_age_days
+        );
+        return Ok(());
+    }
+
+    if !outdated.is_empty() {
+        println!("Pinned to an old version (update needed):");
+        for line in &outdated {
+            println!("  - {}", line);
+        }
+    }
+    if !unmaintained.is_empty() {
+        println!("Old latest version (crate may be unmaintained):");
+        for line in &unmaintained {
+            println!("  - {}", line);
+        }
+    }
+
+    Err(format!(
+        "{} locked versions are older than {} days",
+        outdated.len() + unmaintained.len(),
+        max_age_days
+    )
+    .into())
+}
@@ -0,0 +1,217 @@
+use crate::commands::installed_targets;
+use crate::{extract_missing_crates, manifest, source};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use toml_edit::Item;
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Crates that rely on OS APIs wasm32-unknown-unknown doesn't have.
+const WASM_INCOMPATIBLE: &[(&str, &str)] = &[
+    ("mio", "needs OS sockets and event queues"),
+    ("nix", "wraps Unix system calls"),
+    ("winapi", "wraps Windows system calls"),
+    ("windows-sys", "wraps Windows system calls"),
+    ("openssl", "links the native OpenSSL library"),
+    ("native-tls", "uses the platform TLS library"),
+    ("rusqlite", "links the native SQLite library"),
+    ("notify", "watches the native file system"),
+    ("tokio-uring", "needs Linux io_uring"),
+    ("dirs", "reads OS user directories"),
+];
+
+/// Crates that build for wasm only with a wasm-specific feature enabled:
+/// (crate, feature, why).
+const WASM_FEATURES: &[(&str, &str, &str)] = &[
+    (
+        "getrandom",
+        "js",
+        "to source entropy from the browser (`wasm_js` from 0.3)",
+    ),
+    ("uuid", "js", "so random UUIDs can use the browser RNG"),
+    (
+        "chrono",
+        "wasmbind",
+        "so Utc::now() reads the JavaScript clock",
+    ),
+    (
+        "instant",
+        "wasm-bindgen",
+        "so Instant::now() reads performance.now()",
+    ),
+    (
+        "parking_lot",
+        "wasm-bindgen",
+        "for time-based locking on wasm",
+    ),
+    (
+        "ring",
+        "wasm32_unknown_unknown_js",
+        "for entropy on wasm32-unknown-unknown",
+    ),
+];
+
+/// std APIs that compile for wasm32-unknown-unknown but fail or panic at
+/// runtime: (pattern, description).
+const WASM_INCOMPATIBLE_APIS: &[(&str, &str)] = &[
+    (
+        r"\bstd::fs\b|\bfs::(read|write|File|create_dir|remove_)",
+        "file system access",
+    ),
+    (
+        r"\bstd::net\b|\b(TcpStream|TcpListener|UdpSocket)\b",
+        "network sockets",
+    ),
+    (r"\bstd::process\b|\bCommand::new\b", "spawning processes"),
+    (r"\bthread::spawn\b", "spawning threads"),
+    (
+        r"\bInstant::now\(\)|\bSystemTime::now\(\)",
+        "reading the system clock",
+    ),
+    (r"\benv::var\b", "environment variables"),
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let mut problems = 0;
+
+    // Features may be enabled in a wasm-only target section, so collect them
+    // from every table.
+    let all_features = |package: &str| -> Vec<String> {
+        manifest::dependency_tables(&doc)
+            .iter()
+            .flat_map(|(_, table)| table.iter())
+            .filter(|(key, item)| manifest::package_name(key, item) == package)
+            .flat_map(|(_, item)| enabled_features(item))
+            .collect()
+    };
+    let dependencies: Vec<(String, Vec<String>)> = doc
+        .get("dependencies")
+        .and_then(Item::as_table_like)
+        .map(|table| {
+            table
+                .iter()
+                .map(|(key, item)| manifest::package_name(key, item))
+                .map(|package| {
+                    let features = all_features(&package);
+                    (package, features)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for (package, features) in &dependencies {
+        if let Some((_, reason)) = WASM_INCOMPATIBLE.iter().find(|(name, _)| name == package) {
+            problems += 1;
+            println!("✗ {} doesn't support WASM: {}", package, reason);
+        }
+        if let Some((_, feature, why)) = WASM_FEATURES
+            .iter()
+            .find(|(name, feature, _)| name == package && !features.iter().any(|f| f == feature))
+        {
+            problems += 1;
+            println!(
+                "✗ {} needs the `{}` feature on WASM {} (add it under [target.'cfg(target_arch = \"wasm32\")'.dependencies])",
+                package, feature, why
+            );
+        }
+    }
+
+    let mut used_apis = Vec::new();
+    for (pattern, description) in WASM_INCOMPATIBLE_APIS {
+        let regex = Regex::new(pattern)?;
+        for file in source::rust_files(Path::new("src")) {
+            let content = fs::read_to_string(&file)?;
+            for (index, line) in content.lines().enumerate() {
+                if regex.is_match(line) && !line.trim_start().starts_with("//") {
+                    used_apis.push((*description, format!("{}:{}", file.display(), index + 1)));
+                }
+            }
+        }
+    }
+    if !used_apis.is_empty() {
+        problems += used_apis.len();
+        println!("\nSource using APIs that don't work on {}:", WASM_TARGET);
+        for (description, location) in &used_apis {
+            println!("  - {} ({})", location, description);
+        }
+    }
+
+    if installed_targets().iter().any(|t| t == WASM_TARGET) {
+        println!("\nRunning cargo check --target {}...", WASM_TARGET);
+        let output = Command::new("cargo")
+            .args(["check", "--target", WASM_TARGET])
+            .output()?;
+        if output.status.success() {
+            println!("✓ The project compiles for {}", WASM_TARGET);
+        } else {
+            problems += 1;
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let failed_crate = Regex::new(r"could not compile `([^`]+)`")?;
+            for cap in failed_crate.captures_iter(&stderr) {
+                println!("✗ {} fails to compile for {}", &cap[1], WASM_TARGET);
+            }
+            let missing = extract_missing_crates(&stderr);
+            if !missing.is_empty() {
+                println!(
+                    "✗ Missing crates on {}: {}",
+                    WASM_TARGET,
+                    missing.join(", ")
+                );
+            }
+        }
+    } else {
+        println!(
+            "\nSkipping compilation: run `rustup target add {}` to check the build too.",
+            WASM_TARGET
+        );
+    }
+
+    let has = |name: &str| dependencies.iter().any(|(package, _)| package == name);
+    let mut suggestions = Vec::new();
+    if !has("wasm-bindgen") {
+        suggestions.push("wasm-bindgen to export functions to and call functions from JavaScript");
+    }
+    if !has("web-sys")
+        && used_apis
+            .iter()
+            .any(|(d, _)| *d != "reading the system clock")
+    {
+        suggestions.push("web-sys for browser replacements of file, network and process APIs (fetch, storage, workers)");
+    }
+    if !has("js-sys")
+        && used_apis
+            .iter()
+            .any(|(d, _)| *d == "reading the system clock")
+    {
+        suggestions.push("js-sys for JavaScript's Date and performance clocks");
+    }
+
+    if problems == 0 {
+        println!("\n✓ No WASM compatibility issues found.");
+    }
+    if problems > 0 && !suggestions.is_empty() {
+        println!("\nSuggested crates for WASM support:");
+        for suggestion in &suggestions {
+            println!("  - {}", suggestion);
+        }
+    }
+
+    Ok(())
+}
+
+/// Features explicitly enabled on a dependency entry.
+fn enabled_features(item: &Item) -> Vec<String> {
+    item.as_table_like()
+        .and_then(|spec| spec.get("features"))
+        .and_then(Item::as_array)
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|f| f.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
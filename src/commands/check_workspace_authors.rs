@@ -0,0 +1,179 @@
+use crate::commands::confirm;
+use crate::{manifest, metadata};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{InlineTable, Item, value};
+
+struct Member {
+    name: String,
+    manifest_path: PathBuf,
+    /// `None` when the member inherits with `authors.workspace = true`
+    authors: Option<Vec<String>>,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let root = PathBuf::from(metadata["workspace_root"].as_str().unwrap_or("."));
+    let root_doc = manifest::read_manifest_at(&root.join("Cargo.toml"))?;
+    let workspace_authors = root_doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("authors"))
+        .map(string_array);
+
+    let mut members = Vec::new();
+    for package in metadata::workspace_packages(&metadata) {
+        let (Some(name), Some(manifest_path)) =
+            (package["name"].as_str(), package["manifest_path"].as_str())
+        else {
+            continue;
+        };
+        let doc = manifest::read_manifest_at(Path::new(manifest_path))?;
+        let authors = doc
+            .get("package")
+            .and_then(|package| package.get("authors"));
+        let inherited = authors
+            .and_then(|authors| authors.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false);
+
+        members.push(Member {
+            name: name.to_string(),
+            manifest_path: PathBuf::from(manifest_path),
+            authors: if inherited {
+                None
+            } else {
+                Some(authors.map(string_array).unwrap_or_default())
+            },
+        });
+    }
+
+    if members.len() < 2 {
+        println!("Not a multi-member workspace; nothing to compare.");
+        return Ok(());
+    }
+
+    let Some(workspace_authors) = workspace_authors else {
+        return report_without_workspace_authors(&members);
+    };
+
+    println!(
+        "[workspace.package] authors = {}\n",
+        format_authors(&workspace_authors)
+    );
+    let mut duplicated = Vec::new();
+    let mut problems = 0;
+    for member in &members {
+        match &member.authors {
+            None => println!("✓ {} uses authors.workspace = true", member.name),
+            Some(authors) if authors.is_empty() => {
+                problems += 1;
+                println!(
+                    "✗ {} doesn't declare authors; add authors.workspace = true",
+                    member.name
+                );
+            }
+            Some(authors) if *authors == workspace_authors => {
+                problems += 1;
+                println!(
+                    "✗ {} repeats the workspace authors instead of using authors.workspace = true",
+                    member.name
+                );
+                duplicated.push(member);
+            }
+            Some(authors) => {
+                problems += 1;
+                println!(
+                    "✗ {} lists different authors: {}",
+                    member.name,
+                    format_authors(authors)
+                );
+            }
+        }
+    }
+
+    if problems == 0 {
+        println!(
+            "\n✓ All {} members inherit the workspace authors.",
+            members.len()
+        );
+        return Ok(());
+    }
+
+    if duplicated.is_empty()
+        || !confirm("\nReplace the repeated lists with authors.workspace = true?")
+    {
+        return Ok(());
+    }
+
+    for member in duplicated {
+        let mut doc = manifest::read_manifest_at(&member.manifest_path)?;
+        let mut inherit = InlineTable::new();
+        inherit.insert("workspace", true.into());
+        doc["package"]["authors"] = value(inherit);
+        manifest::write_manifest_at(&member.manifest_path, &doc)?;
+        println!("✓ {} now inherits the workspace authors", member.name);
+    }
+
+    Ok(())
+}
+
+/// Without `[workspace.package] authors`, members can only be compared with
+/// each other.
+fn report_without_workspace_authors(members: &[Member]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut groups: BTreeMap<Vec<String>, Vec<&str>> = BTreeMap::new();
+    for member in members {
+        groups
+            .entry(member.authors.clone().unwrap_or_default())
+            .or_default()
+            .push(&member.name);
+    }
+
+    if groups.len() == 1 {
+        let authors = groups.keys().next().cloned().unwrap_or_default();
+        println!(
+            "✓ All {} members list the same authors: {}",
+            members.len(),
+            format_authors(&authors)
+        );
+    } else {
+        println!("✗ Members list different authors:");
+        for (authors, names) in &groups {
+            println!("  - {}: {}", format_authors(authors), names.join(", "));
+        }
+    }
+
+    let majority = groups
+        .iter()
+        .filter(|(authors, _)| !authors.is_empty())
+        .max_by_key(|(_, names)| names.len())
+        .map(|(authors, _)| authors.clone());
+    if let Some(authors) = majority {
+        println!("\nDeclare them once in the workspace root and inherit them in each member:\n");
+        println!("    [workspace.package]");
+        println!("    authors = {}\n", format_authors(&authors));
+        println!("    [package]");
+        println!("    authors.workspace = true");
+    }
+
+    Ok(())
+}
+
+fn string_array(item: &Item) -> Vec<String> {
+    item.as_array()
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|author| author.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn format_authors(authors: &[String]) -> String {
+    if authors.is_empty() {
+        return "(none)".to_string();
+    }
+    let quoted: Vec<String> = authors.iter().map(|a| format!("\"{}\"", a)).collect();
+    format!("[{}]", quoted.join(", "))
+}
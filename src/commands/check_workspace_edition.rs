@@ -0,0 +1,109 @@
+use crate::commands::confirm;
+use crate::{manifest, metadata};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{Item, value};
+
+struct Member {
+    name: String,
+    manifest_path: PathBuf,
+    edition: String,
+    inherited: bool,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let root = PathBuf::from(metadata["workspace_root"].as_str().unwrap_or("."));
+    let root_manifest = root.join("Cargo.toml");
+    let root_doc = manifest::read_manifest_at(&root_manifest)?;
+    let workspace_edition = root_doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("edition"))
+        .and_then(Item::as_str)
+        .map(str::to_string);
+
+    let mut members = Vec::new();
+    for package in metadata::workspace_packages(&metadata) {
+        let (Some(name), Some(manifest_path)) =
+            (package["name"].as_str(), package["manifest_path"].as_str())
+        else {
+            continue;
+        };
+        let doc = manifest::read_manifest_at(Path::new(manifest_path))?;
+        let edition = doc
+            .get("package")
+            .and_then(|package| package.get("edition"));
+
+        // `edition.workspace = true` takes the value from [workspace.package]
+        let inherited = edition
+            .and_then(|edition| edition.get("workspace"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false);
+        let edition = if inherited {
+            workspace_edition
+                .clone()
+                .unwrap_or_else(|| "2015".to_string())
+        } else {
+            edition.and_then(Item::as_str).unwrap_or("2015").to_string()
+        };
+
+        members.push(Member {
+            name: name.to_string(),
+            manifest_path: PathBuf::from(manifest_path),
+            edition,
+            inherited,
+        });
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for member in &members {
+        *counts.entry(member.edition.as_str()).or_default() += 1;
+    }
+
+    if counts.len() <= 1 {
+        let edition = counts.keys().next().copied().unwrap_or("2015");
+        println!("✓ All {} members use edition {}.", members.len(), edition);
+        return Ok(());
+    }
+
+    let majority = counts
+        .iter()
+        .max_by_key(|(edition, count)| (**count, **edition))
+        .map(|(edition, _)| edition.to_string())
+        .unwrap_or_default();
+    let newest = counts
+        .keys()
+        .last()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+
+    println!("Members use different editions (majority: {}):", majority);
+    for member in members.iter().filter(|m| m.edition != majority) {
+        println!("  - {} uses edition {}", member.name, member.edition);
+    }
+
+    if !confirm(&format!("\nUpdate all members to edition {}?", newest)) {
+        return Ok(());
+    }
+
+    let mut root_changed = false;
+    for member in members.iter().filter(|m| m.edition != newest) {
+        if member.inherited {
+            root_changed = true;
+        } else {
+            let mut doc = manifest::read_manifest_at(&member.manifest_path)?;
+            doc["package"]["edition"] = value(&newest);
+            manifest::write_manifest_at(&member.manifest_path, &doc)?;
+        }
+        println!("✓ Updated {} to edition {}", member.name, newest);
+    }
+    if root_changed {
+        // Re-read so member edits to a root package manifest aren't lost
+        let mut doc = manifest::read_manifest_at(&root_manifest)?;
+        doc["workspace"]["package"]["edition"] = value(&newest);
+        manifest::write_manifest_at(&root_manifest, &doc)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::{manifest, metadata};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let root = PathBuf::from(metadata["workspace_root"].as_str().unwrap_or("."));
+    let root_doc = manifest::read_manifest_at(&root.join("Cargo.toml"))?;
+
+    let Some(workspace) = root_doc.get("workspace").and_then(Item::as_table_like) else {
+        println!("Not a workspace root: Cargo.toml has no [workspace] section.");
+        return Ok(());
+    };
+    if root_doc.get("package").is_some() {
+        println!("Note: the workspace root is also a package, not a virtual manifest.\n");
+    }
+
+    let mut suggestions = Vec::new();
+
+    match workspace.get("resolver").and_then(Item::as_str) {
+        Some("2") | Some("3") => {}
+        Some(resolver) => suggestions.push(format!(
+            "Set `resolver = \"2\"` in [workspace] (currently \"{}\")",
+            resolver
+        )),
+        None => match root_doc.get("package") {
+            None => suggestions.push(
+                "Set `resolver = \"2\"` in [workspace]; virtual manifests default to resolver 1"
+                    .to_string(),
+            ),
+            // A root package picks the default resolver from its edition
+            Some(package) => {
+                let edition = package
+                    .get("edition")
+                    .and_then(Item::as_str)
+                    .or_else(|| {
+                        // `edition.workspace = true` inherits [workspace.package]
+                        workspace
+                            .get("package")
+                            .and_then(|shared| shared.get("edition"))
+                            .and_then(Item::as_str)
+                    })
+                    .unwrap_or("2015");
+                if edition < "2021" {
+                    suggestions.push(format!(
+                        "Set `resolver = \"2\"` in [workspace]; the root package's edition {} defaults to resolver 1",
+                        edition
+                    ));
+                }
+            }
+        },
+    }
+
+    let members: Vec<&str> = workspace
+        .get("members")
+        .and_then(Item::as_array)
+        .map(|members| members.iter().filter_map(|m| m.as_str()).collect())
+        .unwrap_or_default();
+    for member in members.iter().filter(|m| m.contains(['*', '?', '['])) {
+        suggestions.push(format!(
+            "List members explicitly instead of the glob \"{}\", so new directories aren't picked up by accident",
+            member
+        ));
+    }
+
+    suggestions.extend(shared_dependency_suggestions(&metadata, &root_doc)?);
+
+    let has_dev_package_profile = root_doc
+        .get("profile")
+        .and_then(|profile| profile.get("dev"))
+        .and_then(|dev| dev.get("package"))
+        .is_some();
+    if !has_dev_package_profile {
+        suggestions.push(
+            "Add a [profile.dev.package.\"*\"] section (e.g. `debug = false`) to speed up debug builds of heavy dependencies"
+                .to_string(),
+        );
+    }
+
+    if suggestions.is_empty() {
+        println!("✓ The workspace root follows the recommended practices.");
+        return Ok(());
+    }
+
+    println!("Suggestions for the workspace root:");
+    for suggestion in &suggestions {
+        println!("  - {}", suggestion);
+    }
+
+    Ok(())
+}
+
+/// Dependencies whose version is spelled out in member manifests even though
+/// it could be declared once in `[workspace.dependencies]`.
+fn shared_dependency_suggestions(
+    metadata: &serde_json::Value,
+    root_doc: &DocumentMut,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let workspace_deps: Vec<String> = root_doc
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(Item::as_table_like)
+        .map(|deps| {
+            deps.iter()
+                .map(|(key, item)| manifest::package_name(key, item))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Package -> members that declare their own version of it
+    let mut explicit: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in metadata::workspace_packages(metadata) {
+        let (Some(name), Some(manifest_path)) =
+            (package["name"].as_str(), package["manifest_path"].as_str())
+        else {
+            continue;
+        };
+        let doc = manifest::read_manifest_at(Path::new(manifest_path))?;
+        for (_, table) in manifest::dependency_tables(&doc) {
+            for (key, item) in table.iter() {
+                let inherited = item
+                    .as_table_like()
+                    .and_then(|spec| spec.get("workspace"))
+                    .and_then(Item::as_bool)
+                    .unwrap_or(false);
+                let has_version = item.is_str()
+                    || item
+                        .as_table_like()
+                        .is_some_and(|spec| spec.contains_key("version"));
+                if inherited || !has_version {
+                    continue;
+                }
+
+                let members = explicit
+                    .entry(manifest::package_name(key, item))
+                    .or_default();
+                if !members.iter().any(|m| m == name) {
+                    members.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    let mut suggestions = Vec::new();
+    for (package, members) in explicit {
+        if workspace_deps.contains(&package) {
+            suggestions.push(format!(
+                "Use `{} = {{ workspace = true }}` in {} instead of repeating the version",
+                package,
+                members.join(", ")
+            ));
+        } else if members.len() > 1 {
+            suggestions.push(format!(
+                "Move {} to [workspace.dependencies]; its version is repeated in {}",
+                package,
+                members.join(", ")
+            ));
+        }
+    }
+    Ok(suggestions)
+}
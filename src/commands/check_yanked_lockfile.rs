@@ -0,0 +1,138 @@
+use crate::lockfile::{self, LOCKFILE_PATH};
+use crate::{cratesio, metadata};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long downloaded index entries are reused before asking crates.io again.
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let packages: Vec<_> = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?
+        .into_iter()
+        .filter(|package| package.is_from_crates_io())
+        .collect();
+
+    println!(
+        "Checking {} locked crates.io packages for yanked versions...\n",
+        packages.len()
+    );
+
+    let mut yanked = Vec::new();
+    let mut unchecked = 0;
+    for package in &packages {
+        let entries = match cratesio::cached_index_entries(&package.name, CACHE_TTL) {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("✗ Could not fetch {}: {}", package.name, e);
+                unchecked += 1;
+                continue;
+            }
+        };
+        let is_yanked = entries
+            .iter()
+            .find(|entry| entry["vers"] == package.version.as_str())
+            .is_some_and(|entry| entry["yanked"].as_bool().unwrap_or(false));
+        if is_yanked {
+            yanked.push(package);
+        }
+    }
+
+    if yanked.is_empty() {
+        if unchecked == 0 {
+            println!("✓ No locked package is yanked.");
+        } else {
+            println!("\nNo yanked packages among the ones that could be checked.");
+        }
+        return Ok(());
+    }
+
+    let metadata = metadata::cargo_metadata(&[])?;
+    println!("Yanked packages in Cargo.lock:");
+    for package in &yanked {
+        let chain = dependency_chain(&metadata, &package.name, &package.version)
+            .map(|chain| chain.join(" -> "))
+            .unwrap_or_else(|| "not reachable from the workspace".to_string());
+        println!("  - {} {}", package.name, package.version);
+        println!("      pulled in by: {}", chain);
+    }
+    println!("\nRun `cargo update -p <name>` to move off a yanked version.");
+
+    Err(format!("{} yanked package(s) in Cargo.lock", yanked.len()).into())
+}
+
+/// Shortest path from a workspace member to `name version`, found with a
+/// breadth-first search over the resolved graph.
+fn dependency_chain(metadata: &Value, name: &str, version: &str) -> Option<Vec<String>> {
+    let labels: HashMap<&str, String> = metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            Some((
+                package["id"].as_str()?,
+                format!(
+                    "{} {}",
+                    package["name"].as_str()?,
+                    package["version"].as_str()?
+                ),
+            ))
+        })
+        .collect();
+    let target = format!("{} {}", name, version);
+
+    let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in metadata["resolve"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let Some(id) = node["id"].as_str() else {
+            continue;
+        };
+        edges.insert(
+            id,
+            node["dependencies"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect(),
+        );
+    }
+
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut queue: VecDeque<&str> = metadata["workspace_members"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let roots: Vec<&str> = queue.iter().copied().collect();
+
+    while let Some(id) = queue.pop_front() {
+        if labels.get(id) == Some(&target) {
+            let mut chain = vec![labels[id].clone()];
+            let mut current = id;
+            while let Some(parent) = parents.get(current) {
+                chain.push(
+                    labels
+                        .get(parent)
+                        .cloned()
+                        .unwrap_or_else(|| parent.to_string()),
+                );
+                current = parent;
+            }
+            chain.reverse();
+            return Some(chain);
+        }
+        for dep in edges.get(id).into_iter().flatten() {
+            if !roots.contains(dep) && !parents.contains_key(dep) {
+                parents.insert(dep, id);
+                queue.push_back(dep);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,166 @@
+use crate::commands::{cargo_home, confirm, format_size};
+use crate::metadata;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct CachedRepo {
+    name: String,
+    checkouts: Vec<PathBuf>,
+    db: Option<PathBuf>,
+    used: bool,
+}
+
+pub fn run(unused: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let git_dir = cargo_home().join("git");
+    if !git_dir.exists() {
+        println!("No git dependencies cached in {}.", git_dir.display());
+        return Ok(());
+    }
+
+    let used_revs = used_git_revs()?;
+    let repos = cached_repos(&git_dir, &used_revs);
+    if repos.is_empty() {
+        println!("No git dependencies cached in {}.", git_dir.display());
+        return Ok(());
+    }
+
+    println!("Cached git dependencies in {}:", git_dir.display());
+    for repo in &repos {
+        let status = if repo.used { "in use" } else { "unused" };
+        println!(
+            "  - {} ({}, {})",
+            repo.name,
+            status,
+            format_size(repo_size(repo))
+        );
+    }
+
+    let stale: Vec<&CachedRepo> = repos.iter().filter(|repo| !repo.used).collect();
+    if stale.is_empty() {
+        println!("\nEvery cached git dependency is used by this project.");
+        return Ok(());
+    }
+
+    let freed: u64 = stale.iter().map(|repo| repo_size(repo)).sum();
+    if !unused {
+        println!(
+            "\n{} unused repositories ({}). Run with --unused to remove them.",
+            stale.len(),
+            format_size(freed)
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\nRemoving {} unused repositories would free {}.",
+        stale.len(),
+        format_size(freed)
+    );
+    println!("Other projects that still use them will re-fetch them on their next build.");
+    if !confirm("Remove them?") {
+        println!("Nothing removed.");
+        return Ok(());
+    }
+
+    for repo in stale {
+        for path in repo.checkouts.iter().chain(repo.db.iter()) {
+            match fs::remove_dir_all(path) {
+                Ok(()) => println!("✓ Removed {}", path.display()),
+                Err(e) => println!("✗ Failed to remove {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Commit hashes of every git package resolved for the current workspace.
+fn used_git_revs() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&[])?;
+    let revs = metadata::packages(&metadata)
+        .iter()
+        .filter_map(|package| package["source"].as_str())
+        .filter(|source| source.starts_with("git+"))
+        .filter_map(|source| source.rsplit_once('#').map(|(_, rev)| rev.to_string()))
+        .collect();
+    Ok(revs)
+}
+
+/// Groups `git/checkouts/<name>/<short-rev>` and `git/db/<name>` by name.
+/// A repository is in use when one of its checked out revisions is.
+fn cached_repos(git_dir: &Path, used_revs: &[String]) -> Vec<CachedRepo> {
+    let mut repos: Vec<CachedRepo> = Vec::new();
+
+    for entry in read_dirs(&git_dir.join("checkouts")) {
+        let name = entry
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let checkouts = read_dirs(&entry);
+        let used = checkouts.iter().any(|checkout| {
+            let short = checkout.file_name().unwrap_or_default().to_string_lossy();
+            used_revs.iter().any(|rev| rev.starts_with(short.as_ref()))
+        });
+        repos.push(CachedRepo {
+            name,
+            checkouts: vec![entry],
+            db: None,
+            used,
+        });
+    }
+
+    for entry in read_dirs(&git_dir.join("db")) {
+        let name = entry
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        match repos.iter_mut().find(|repo| repo.name == name) {
+            Some(repo) => repo.db = Some(entry),
+            None => repos.push(CachedRepo {
+                name,
+                checkouts: Vec::new(),
+                db: Some(entry),
+                used: false,
+            }),
+        }
+    }
+
+    repos.sort_by(|a, b| a.name.cmp(&b.name));
+    repos
+}
+
+fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn repo_size(repo: &CachedRepo) -> u64 {
+    repo.checkouts
+        .iter()
+        .chain(repo.db.iter())
+        .map(|path| dir_size(path))
+        .sum()
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
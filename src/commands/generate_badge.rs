@@ -0,0 +1,74 @@
+use crate::extract_missing_crates;
+use std::fs;
+use std::process::Command;
+
+const BADGE_PATH: &str = "dependency-badge.svg";
+
+pub fn run(shields_url: bool) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Checking dependency health...");
+    let output = Command::new("cargo")
+        .args(["check", "--all-targets"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let issues = extract_missing_crates(&stderr).len();
+
+    let (message, color, hex) = match issues {
+        // A build broken by anything other than missing crates isn't healthy
+        0 if !output.status.success() => ("build failing".to_string(), "red", "#e05d44"),
+        0 => ("healthy".to_string(), "green", "#4c1"),
+        1 => ("1 issue".to_string(), "yellow", "#dfb317"),
+        2..=3 => (format!("{} issues", issues), "yellow", "#dfb317"),
+        _ => (format!("{} issues", issues), "red", "#e05d44"),
+    };
+
+    if shields_url {
+        let url = format!(
+            "https://img.shields.io/badge/deps-{}-{}",
+            message.replace(' ', "%20"),
+            color
+        );
+        println!("{}", url);
+        println!("\nMarkdown: ![deps]({})", url);
+        return Ok(());
+    }
+
+    fs::write(BADGE_PATH, badge_svg("deps", &message, hex))?;
+    println!("✓ Wrote {} (deps | {})", BADGE_PATH, message);
+    println!("\nMarkdown: ![deps]({})", BADGE_PATH);
+    Ok(())
+}
+
+/// A flat shields.io-style badge. Widths are estimated from the text length,
+/// which is close enough for the short labels used here.
+fn badge_svg(label: &str, message: &str, color: &str) -> String {
+    let label_width = label.len() * 7 + 10;
+    let message_width = message.len() * 7 + 10;
+    let width = label_width + message_width;
+    let label_x = label_width / 2;
+    let message_x = label_width + message_width / 2;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r">
+    <rect width="{width}" height="20" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##
+    )
+}
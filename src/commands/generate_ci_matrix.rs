@@ -0,0 +1,371 @@
+use crate::lockfile::LOCKFILE_PATH;
+use crate::manifest;
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+const CHANNELS: &[&str] = &["stable", "beta", "nightly"];
+
+/// One row of the feature dimension: a short name for job titles and the
+/// flags passed to cargo.
+struct Combination {
+    name: String,
+    flags: String,
+}
+
+pub fn run(
+    provider: &str,
+    matrix: &[String],
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = match provider {
+        "github" => ".github/workflows/ci-matrix.yml",
+        "gitlab" => ".gitlab-ci.yml",
+        "circleci" => ".circleci/config.yml",
+        other => {
+            return Err(
+                format!("unknown provider {}; use github, gitlab or circleci", other).into(),
+            );
+        }
+    };
+
+    let doc = manifest::read_manifest()?;
+    let combinations = combinations(&doc, matrix)?;
+    let mut toolchains: Vec<String> = CHANNELS.iter().map(|c| c.to_string()).collect();
+    // Channels follow the latest release on purpose; only the MSRV is pinned,
+    // as its own row so it can't drift unnoticed
+    if let Some(msrv) = rust_version(&doc) {
+        toolchains.push(msrv);
+    }
+    let locked = if Path::new(LOCKFILE_PATH).exists() {
+        " --locked"
+    } else {
+        ""
+    };
+
+    let yaml = match provider {
+        "github" => github(&toolchains, &combinations, locked),
+        "gitlab" => gitlab(&toolchains, &combinations, locked),
+        _ => circleci(&toolchains, &combinations, locked),
+    };
+
+    if dry_run {
+        print!("{}", yaml);
+        return Ok(());
+    }
+    if Path::new(output).exists() {
+        return Err(format!(
+            "{} already exists; use --dry-run to print the configuration instead",
+            output
+        )
+        .into());
+    }
+    if let Some(parent) = Path::new(output).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, yaml)?;
+
+    println!(
+        "✓ Wrote {} with {} jobs (3 platforms × {} toolchains × {} feature sets)",
+        output,
+        3 * toolchains.len() * combinations.len(),
+        toolchains.len(),
+        combinations.len()
+    );
+    println!("    toolchains: {}", toolchains.join(", "));
+    if toolchains.len() > CHANNELS.len() {
+        println!(
+            "                ({} is pinned from rust-version; the channels track their latest release)",
+            toolchains[CHANNELS.len()]
+        );
+    } else {
+        println!(
+            "                (the channels track their latest release; set rust-version to pin an MSRV row)"
+        );
+    }
+    for combination in &combinations {
+        println!("    features:   {}", combination.name);
+    }
+    println!("\nNightly failures are reported without failing the pipeline.");
+    Ok(())
+}
+
+/// Feature sets from `--matrix`, or default / all / none when none are given.
+/// `default`, `all` and `none` stand for cargo's own flags; anything else is a
+/// comma-separated list of features.
+fn combinations(
+    doc: &DocumentMut,
+    matrix: &[String],
+) -> Result<Vec<Combination>, Box<dyn std::error::Error>> {
+    let features = manifest::feature_names(doc);
+    let entries = manifest::feature_entries(doc);
+    // Optional dependencies not referenced as `dep:` are features too
+    let is_feature = |name: &str| {
+        features.iter().any(|f| f == name)
+            || (manifest::is_optional_dependency(doc, name)
+                && manifest::has_implicit_feature(&entries, name))
+    };
+    let has_default = doc
+        .get("features")
+        .and_then(|features| features.get("default"))
+        .is_some();
+
+    let requested: Vec<String> = if matrix.is_empty() {
+        let mut defaults = vec!["default".to_string()];
+        if !features.is_empty() {
+            defaults.push("all".to_string());
+        }
+        if has_default {
+            defaults.push("none".to_string());
+        }
+        defaults
+    } else {
+        matrix.to_vec()
+    };
+
+    let mut combinations = Vec::new();
+    for entry in &requested {
+        let flags = match entry.as_str() {
+            "default" => String::new(),
+            "all" => "--all-features".to_string(),
+            "none" => "--no-default-features".to_string(),
+            list => {
+                let names: Vec<&str> = list
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .collect();
+                for name in &names {
+                    // `dep/feature` refers to a dependency's feature list
+                    if !name.contains('/') && !is_feature(name) {
+                        return Err(format!("unknown feature {} in --matrix {}", name, list).into());
+                    }
+                }
+                format!("--features {}", names.join(","))
+            }
+        };
+        if !combinations.iter().any(|c: &Combination| c.flags == flags) {
+            combinations.push(Combination {
+                name: entry.clone(),
+                flags,
+            });
+        }
+    }
+    Ok(combinations)
+}
+
+/// `rust-version` of the package, or the inherited `[workspace.package]` one.
+fn rust_version(doc: &DocumentMut) -> Option<String> {
+    doc.get("package")
+        .and_then(|package| package.get("rust-version"))
+        .and_then(Item::as_str)
+        .or_else(|| {
+            doc.get("workspace")
+                .and_then(|workspace| workspace.get("package"))
+                .and_then(|package| package.get("rust-version"))
+                .and_then(Item::as_str)
+        })
+        .map(str::to_string)
+}
+
+/// `["a", "b"]`, quoted so versions like 1.70 stay strings.
+fn yaml_list<'a>(items: impl IntoIterator<Item = &'a str>) -> String {
+    let quoted: Vec<String> = items
+        .into_iter()
+        .map(|item| format!("\"{}\"", item))
+        .collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+fn github(toolchains: &[String], combinations: &[Combination], locked: &str) -> String {
+    let features: String = combinations
+        .iter()
+        .map(|c| {
+            format!(
+                "          - {{ name: \"{}\", flags: \"{}\" }}\n",
+                c.name, c.flags
+            )
+        })
+        .collect();
+
+    format!(
+        r#"# Generated by cargo tidy generate-ci-matrix
+name: CI matrix
+
+on:
+  push:
+    branches: [main, master]
+  pull_request:
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  test:
+    name: ${{{{ matrix.os }}}} / ${{{{ matrix.rust }}}} / ${{{{ matrix.features.name }}}}
+    runs-on: ${{{{ matrix.os }}}}
+    # Nightly breakage shows up in the run without failing the workflow
+    continue-on-error: ${{{{ matrix.rust == 'nightly' }}}}
+    strategy:
+      # Finish every combination so one failure doesn't hide the others
+      fail-fast: false
+      matrix:
+        os: [ubuntu-latest, macos-latest, windows-latest]
+        rust: {toolchains}
+        features:
+{features}    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@master
+        with:
+          toolchain: ${{{{ matrix.rust }}}}
+      - uses: Swatinem/rust-cache@v2
+        with:
+          key: ${{{{ matrix.rust }}}}-${{{{ matrix.features.name }}}}
+      - name: Build
+        run: cargo build{locked} --all-targets ${{{{ matrix.features.flags }}}}
+      - name: Test
+        run: cargo test{locked} --no-fail-fast ${{{{ matrix.features.flags }}}}
+"#,
+        toolchains = yaml_list(toolchains.iter().map(String::as_str)),
+        features = features,
+        locked = locked,
+    )
+}
+
+fn gitlab(toolchains: &[String], combinations: &[Combination], locked: &str) -> String {
+    format!(
+        r#"# Generated by cargo tidy generate-ci-matrix
+stages:
+  - test
+
+variables:
+  CARGO_HOME: $CI_PROJECT_DIR/.cargo
+  CARGO_TERM_COLOR: always
+
+.matrix:
+  parallel:
+    matrix:
+      - RUST: {toolchains}
+        FEATURES: {features}
+  # Nightly breakage shows up in the pipeline without failing it
+  rules:
+    - if: $RUST == "nightly"
+      allow_failure: true
+    - when: on_success
+  cache:
+    key: $CI_JOB_NAME_SLUG
+    paths:
+      - .cargo/registry
+      - target/
+
+test:linux:
+  extends: .matrix
+  stage: test
+  image: rust:latest
+  script:
+    - rustup toolchain install "$RUST" --profile minimal
+    - cargo +"$RUST" build{locked} --all-targets $FEATURES
+    - cargo +"$RUST" test{locked} --no-fail-fast $FEATURES
+
+test:macos:
+  extends: .matrix
+  stage: test
+  tags: [saas-macos-medium-m1]
+  image: macos-14-xcode-15
+  script:
+    - curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y --profile minimal --default-toolchain "$RUST"
+    - . "$CARGO_HOME/env"
+    - cargo build{locked} --all-targets $FEATURES
+    - cargo test{locked} --no-fail-fast $FEATURES
+
+test:windows:
+  extends: .matrix
+  stage: test
+  tags: [saas-windows-medium-amd64]
+  script:
+    - Invoke-WebRequest https://win.rustup.rs/x86_64 -OutFile rustup-init.exe
+    - .\rustup-init.exe -y --profile minimal --default-toolchain $env:RUST
+    - $env:PATH = "$env:CARGO_HOME\bin;$env:PATH"
+    # Invoke-Expression splits $FEATURES into separate arguments, as sh does
+    - Invoke-Expression "cargo build{locked} --all-targets $env:FEATURES"
+    - Invoke-Expression "cargo test{locked} --no-fail-fast $env:FEATURES"
+"#,
+        toolchains = yaml_list(toolchains.iter().map(String::as_str)),
+        features = yaml_list(combinations.iter().map(|c| c.flags.as_str())),
+        locked = locked,
+    )
+}
+
+fn circleci(toolchains: &[String], combinations: &[Combination], locked: &str) -> String {
+    let checksum_file = if locked.is_empty() {
+        "Cargo.toml"
+    } else {
+        LOCKFILE_PATH
+    };
+
+    format!(
+        r#"# Generated by cargo tidy generate-ci-matrix
+version: 2.1
+
+executors:
+  linux:
+    docker:
+      - image: cimg/base:current
+  macos:
+    macos:
+      xcode: 15.4.0
+  windows:
+    machine:
+      image: windows-server-2022-gui:current
+      shell: bash.exe
+    resource_class: windows.medium
+
+jobs:
+  test:
+    parameters:
+      os:
+        type: executor
+      rust:
+        type: string
+      features:
+        type: string
+    executor: << parameters.os >>
+    steps:
+      - checkout
+      - run:
+          name: Install Rust << parameters.rust >>
+          command: |
+            curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y --profile minimal --default-toolchain << parameters.rust >>
+            echo 'export PATH="$HOME/.cargo/bin:$PATH"' >> "$BASH_ENV"
+      - restore_cache:
+          keys:
+            - cargo-v1-<< parameters.rust >>-{{{{ arch }}}}-{{{{ checksum "{checksum_file}" }}}}
+      # CircleCI has no allowed failures, so nightly breakage only shows in the log
+      - run:
+          name: Build
+          command: cargo build{locked} --all-targets << parameters.features >> || [ "<< parameters.rust >>" = nightly ]
+      - run:
+          name: Test
+          command: cargo test{locked} --no-fail-fast << parameters.features >> || [ "<< parameters.rust >>" = nightly ]
+      - save_cache:
+          key: cargo-v1-<< parameters.rust >>-{{{{ arch }}}}-{{{{ checksum "{checksum_file}" }}}}
+          paths:
+            - ~/.cargo/registry
+            - target
+
+workflows:
+  ci-matrix:
+    jobs:
+      - test:
+          matrix:
+            parameters:
+              os: [linux, macos, windows]
+              rust: {toolchains}
+              features: {features}
+"#,
+        toolchains = yaml_list(toolchains.iter().map(String::as_str)),
+        features = yaml_list(combinations.iter().map(|c| c.flags.as_str())),
+        checksum_file = checksum_file,
+        locked = locked,
+    )
+}
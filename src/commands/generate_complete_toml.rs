@@ -0,0 +1,254 @@
+use crate::{cratesio, is_std_module, manifest, source};
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, InlineTable, Item, Table, value};
+
+/// Features to enable when a crate is used in a way that needs them:
+/// (crate, source pattern, feature).
+const FEATURE_HINTS: &[(&str, &str, &str)] = &[
+    (
+        "serde",
+        r"derive\([^)]*\b(Serialize|Deserialize)\b",
+        "derive",
+    ),
+    (
+        "clap",
+        r"derive\([^)]*\b(Parser|Subcommand|Args|ValueEnum)\b",
+        "derive",
+    ),
+    ("tokio", r"#\[tokio::(main|test)", "macros"),
+    ("tokio", r"#\[tokio::main\]", "rt-multi-thread"),
+    ("tokio", r"tokio::fs::", "fs"),
+    ("tokio", r"tokio::net::", "net"),
+    ("tokio", r"tokio::time::", "time"),
+    ("tokio", r"tokio::sync::", "sync"),
+    ("tokio", r"tokio::process::", "process"),
+    ("tokio", r"tokio::signal::", "signal"),
+    (
+        "tokio",
+        r"tokio::io::(AsyncReadExt|AsyncWriteExt|AsyncBufReadExt)",
+        "io-util",
+    ),
+    ("reqwest", r"\.json\(", "json"),
+    ("reqwest", r"reqwest::blocking", "blocking"),
+    ("uuid", r"Uuid::new_v4", "v4"),
+    ("uuid", r"Uuid::now_v7", "v7"),
+    (
+        "chrono",
+        r"derive\([^)]*\b(Serialize|Deserialize)\b",
+        "serde",
+    ),
+    ("uuid", r"derive\([^)]*\b(Serialize|Deserialize)\b", "serde"),
+    ("sqlx", r"sqlx::(Pg|postgres)", "postgres"),
+    ("sqlx", r"sqlx::(Sqlite|sqlite)", "sqlite"),
+    ("sqlx", r"sqlx::(MySql|mysql)", "mysql"),
+];
+
+/// Where a crate is used decides which table it belongs in.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Usage {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl Usage {
+    fn table(self) -> &'static str {
+        match self {
+            Usage::Normal => "dependencies",
+            Usage::Dev => "dev-dependencies",
+            Usage::Build => "build-dependencies",
+        }
+    }
+}
+
+struct Detected {
+    usage: Usage,
+    features: Vec<String>,
+}
+
+pub fn run(dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut doc = manifest::read_manifest()?;
+    let declared: Vec<String> = manifest::direct_dependencies(&doc)
+        .iter()
+        .map(|name| name.replace('-', "_"))
+        .collect();
+    let own_name = manifest::own_crate_name(&doc);
+
+    let files = source_files();
+    if files.is_empty() {
+        return Err("no Rust source files found".into());
+    }
+
+    let mut sources = Vec::new();
+    for file in &files {
+        sources.push((file, strip_literals(&fs::read_to_string(file)?)));
+    }
+    let local_modules =
+        source::declared_modules(sources.iter().map(|(_, content)| content.as_str()));
+
+    let use_regex = Regex::new(
+        r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?(?:use\s+(?:::)?|extern\s+crate\s+)([a-zA-Z_][a-zA-Z0-9_]*)([^;]*);",
+    )?;
+    // Fully qualified paths, including `#[krate::attr]` and `krate::mac!`
+    let path_regex = Regex::new(r"(?:^|[^:\w])([a-z_][a-z0-9_]*)::[a-zA-Z_]")?;
+    let ident_regex = Regex::new(r"[a-zA-Z_][a-zA-Z0-9_]*")?;
+
+    let mut detected: BTreeMap<String, Detected> = BTreeMap::new();
+    for (file, content) in &sources {
+        let usage = usage_of(file);
+
+        // Modules brought into scope by `use` look like crates in paths
+        let mut imported = HashSet::new();
+        let mut names = Vec::new();
+        for cap in use_regex.captures_iter(content) {
+            names.push(cap[1].to_string());
+            imported.extend(
+                ident_regex
+                    .find_iter(&cap[2])
+                    .map(|ident| ident.as_str().to_string()),
+            );
+        }
+        names.extend(
+            path_regex
+                .captures_iter(content)
+                .map(|cap| cap[1].to_string())
+                .filter(|name| !imported.contains(name)),
+        );
+
+        for name in names {
+            if is_std_module(&name)
+                || ["self", "super", "crate"].contains(&name.as_str())
+                || name == own_name
+                || local_modules.contains(&name)
+                || declared.contains(&name)
+            {
+                continue;
+            }
+            let entry = detected.entry(name).or_insert(Detected {
+                usage,
+                features: Vec::new(),
+            });
+            entry.usage = entry.usage.min(usage);
+        }
+    }
+
+    if detected.is_empty() {
+        println!("✓ Every external crate used in the source is already declared.");
+        return Ok(());
+    }
+
+    for (name, crate_usage) in detected.iter_mut() {
+        for (krate, pattern, feature) in FEATURE_HINTS {
+            if krate.replace('-', "_") != *name || crate_usage.features.iter().any(|f| f == feature)
+            {
+                continue;
+            }
+            let pattern = Regex::new(pattern)?;
+            if sources.iter().any(|(_, content)| pattern.is_match(content)) {
+                crate_usage.features.push(feature.to_string());
+            }
+        }
+    }
+
+    println!("Resolving {} crate(s) on crates.io...\n", detected.len());
+    let mut added = 0;
+    for (name, crate_usage) in &detected {
+        let Some((package, version)) = resolve(name) else {
+            println!(
+                "✗ Could not find `{}` on crates.io; it may be a local module",
+                name
+            );
+            continue;
+        };
+
+        let table = doc
+            .entry(crate_usage.usage.table())
+            .or_insert(Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| format!("[{}] is not a table", crate_usage.usage.table()))?;
+
+        if crate_usage.features.is_empty() {
+            table.insert(&package, value(version.as_str()));
+        } else {
+            let mut spec = InlineTable::new();
+            spec.insert("version", version.as_str().into());
+            spec.insert(
+                "features",
+                Array::from_iter(crate_usage.features.iter().map(String::as_str)).into(),
+            );
+            table.insert(&package, value(spec));
+        }
+
+        let features = if crate_usage.features.is_empty() {
+            String::new()
+        } else {
+            format!(" (features: {})", crate_usage.features.join(", "))
+        };
+        println!(
+            "✓ [{}] {} = \"{}\"{}",
+            crate_usage.usage.table(),
+            package,
+            version,
+            features
+        );
+        added += 1;
+    }
+
+    if added == 0 {
+        return Err("none of the detected crates could be resolved".into());
+    }
+
+    if dry_run {
+        println!("\nGenerated Cargo.toml (dry run, nothing written):\n");
+        println!("{}", doc);
+    } else {
+        manifest::write_manifest(&doc)?;
+        println!("\nWrote {} dependencies to Cargo.toml.", added);
+    }
+
+    Ok(())
+}
+
+/// Sources that belong to the package: the crate itself, its tests, examples
+/// and benchmarks, and the build script.
+fn source_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = ["src", "tests", "examples", "benches"]
+        .iter()
+        .flat_map(|dir| source::rust_files(Path::new(dir)))
+        .collect();
+    if Path::new("build.rs").exists() {
+        files.push(PathBuf::from("build.rs"));
+    }
+    files
+}
+
+/// Blanks out string literals and line comments so crate paths mentioned in
+/// messages or docs aren't mistaken for real uses.
+fn strip_literals(content: &str) -> String {
+    // `(?s)` lets `\\.` match a `\` line continuation inside a string
+    let literal_regex = Regex::new(r##"(?s)r#*"[^"]*"#*|"(?:\\.|[^"\\])*"|//[^\n]*"##).unwrap();
+    literal_regex.replace_all(content, "\"\"").into_owned()
+}
+
+fn usage_of(file: &Path) -> Usage {
+    if file == Path::new("build.rs") {
+        Usage::Build
+    } else if file.starts_with("src") {
+        Usage::Normal
+    } else {
+        Usage::Dev
+    }
+}
+
+/// The crates.io package name and latest version for a crate referenced as
+/// `name` in source.
+fn resolve(name: &str) -> Option<(String, String)> {
+    let (package, entries) = cratesio::find_crate(name)?;
+    let version = cratesio::latest_version(&entries)?;
+    // Build metadata like `+spec-1.1.0` has no meaning in a requirement
+    let version = version.split('+').next()?.to_string();
+    Some((package, version))
+}
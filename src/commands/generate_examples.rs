@@ -0,0 +1,235 @@
+use crate::{cratesio, manifest};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use toml_edit::Item;
+
+pub fn run(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (package, version) = latest_version(name)?;
+    println!("Fetching {} {}...", package, version);
+    let root = cratesio::download_crate(&package, &version)?;
+
+    let mut blocks = Vec::new();
+    let readme = readme_path(&root);
+    if let Ok(content) = fs::read_to_string(&readme) {
+        blocks.extend(markdown_rust_blocks(&content));
+    }
+    if let Ok(content) = fs::read_to_string(root.join("src").join("lib.rs")) {
+        let docs: String = content
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("//!"))
+            .map(|line| format!("{}\n", line.strip_prefix(' ').unwrap_or(line)))
+            .collect();
+        blocks.extend(
+            markdown_rust_blocks(&docs)
+                .into_iter()
+                .map(unhide_doc_lines),
+        );
+    }
+    let mut unique: Vec<String> = Vec::new();
+    for block in blocks {
+        if !unique.contains(&block) {
+            unique.push(block);
+        }
+    }
+    let blocks = unique;
+
+    if blocks.is_empty() {
+        return Err(format!(
+            "no Rust code examples found in the {} documentation",
+            package
+        )
+        .into());
+    }
+
+    let example_name = format!("{}_usage", package.replace('-', "_"));
+    let path = Path::new("examples").join(format!("{}.rs", example_name));
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+
+    fs::create_dir_all("examples")?;
+    fs::write(&path, example_source(&package, &version, &blocks))?;
+    println!(
+        "✓ Wrote {} example(s) from the {} docs to {}",
+        blocks.len(),
+        package,
+        path.display()
+    );
+
+    let doc = manifest::read_manifest()?;
+    if !manifest::direct_dependencies(&doc).contains(&package) {
+        println!("\n{} isn't a dependency yet; add it with:", package);
+        println!("cargo add --dev {}", package);
+    }
+    println!("\nRun it with `cargo run --example {}`.", example_name);
+    println!("README snippets are often partial, so expect to adjust imports or error handling.");
+
+    Ok(())
+}
+
+/// The published name and newest stable version, from the crates.io API with
+/// the sparse index as a fallback.
+fn latest_version(name: &str) -> Result<(String, String), Box<dyn std::error::Error>> {
+    if let Ok(info) = cratesio::crate_info(name)
+        && let (Some(package), Some(version)) = (
+            info["crate"]["name"].as_str(),
+            info["crate"]["max_stable_version"].as_str(),
+        )
+    {
+        return Ok((package.to_string(), version.to_string()));
+    }
+
+    let (package, entries) = cratesio::find_crate(name)
+        .ok_or_else(|| format!("crate {} not found on crates.io", name))?;
+    let version = cratesio::latest_version(&entries)
+        .ok_or_else(|| format!("{} has no stable, non-yanked versions", package))?;
+    Ok((package, version))
+}
+
+/// The README crates.io renders: the manifest's `readme` field, or README.md.
+fn readme_path(root: &Path) -> std::path::PathBuf {
+    let readme = manifest::read_manifest_at(&root.join("Cargo.toml"))
+        .ok()
+        .and_then(|doc| {
+            doc.get("package")
+                .and_then(|package| package.get("readme"))
+                .and_then(Item::as_str)
+                .map(str::to_string)
+        });
+    root.join(readme.unwrap_or_else(|| "README.md".to_string()))
+}
+
+/// Contents of fenced code blocks tagged `rust` (or untagged, which rustdoc
+/// treats as Rust). Blocks that aren't meant to compile are left out.
+fn markdown_rust_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    // `Some(block)` inside a Rust block, `None` inside any other block
+    let mut fence: Option<Option<String>> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```");
+
+        match fence.as_mut() {
+            None if is_fence => {
+                let info = trimmed.trim_start_matches('`').trim();
+                let tags: Vec<&str> = info.split([',', ' ']).filter(|t| !t.is_empty()).collect();
+                let is_rust = tags.is_empty() || tags.iter().any(|t| *t == "rust" || *t == "rs");
+                let skipped = tags.iter().any(|t| ["ignore", "compile_fail"].contains(t));
+                fence = Some((is_rust && !skipped).then(String::new));
+            }
+            None => {}
+            Some(block) if is_fence => {
+                if let Some(block) = block.take()
+                    && !block.trim().is_empty()
+                {
+                    blocks.push(block);
+                }
+                fence = None;
+            }
+            Some(Some(block)) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            Some(None) => {}
+        }
+    }
+
+    blocks
+}
+
+/// Doc tests hide setup lines behind `# `; the example needs them.
+fn unhide_doc_lines(block: String) -> String {
+    block
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed == "#" {
+                ""
+            } else {
+                trimmed.strip_prefix("# ").unwrap_or(line)
+            }
+        })
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// One module per snippet so their imports can't clash, and a `main` that
+/// runs them in order. A snippet's own `main` becomes its module's `run`; an
+/// async one loses its `#[runtime::main]` attribute, which moves to the
+/// example's `main` so that one runtime drives every snippet.
+fn example_source(package: &str, version: &str, blocks: &[String]) -> String {
+    let main_fn = Regex::new(r"(?m)^(\s*)(?:pub\s+)?(async\s+)?fn\s+main\s*\(").unwrap();
+    let main_attr = Regex::new(r"(?m)^[ \t]*#\[\s*[\w:]+::main\b[^\]]*\][ \t]*\n").unwrap();
+
+    let mut source = format!(
+        "//! Usage examples for {} {}, extracted from its README and crate docs.\n\n",
+        package, version
+    );
+
+    let mut runtime = None;
+    let mut is_async = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let body = match main_fn.captures(block) {
+            Some(cap) => {
+                let asynchronous = cap.get(2).is_some();
+                is_async.push(asynchronous);
+                let mut body = block.clone();
+                if let Some(attr) = main_attr.find(&body) {
+                    if asynchronous && runtime.is_none() {
+                        runtime = Some(attr.as_str().trim().to_string());
+                    }
+                    body.replace_range(attr.range(), "");
+                }
+                let signature = if asynchronous {
+                    "pub async fn run("
+                } else {
+                    "pub fn run("
+                };
+                main_fn
+                    .replace(&body, format!("${{1}}{}", signature).as_str())
+                    .into_owned()
+            }
+            None => {
+                is_async.push(false);
+                format!("pub fn run() {{\n{}}}\n", indent(block))
+            }
+        };
+        source.push_str(&format!(
+            "mod example_{} {{\n{}}}\n\n",
+            index + 1,
+            indent(&body)
+        ));
+    }
+
+    match &runtime {
+        Some(attr) => source.push_str(&format!("{}\nasync fn main() {{\n", attr)),
+        None => source.push_str("fn main() {\n"),
+    }
+    for (index, asynchronous) in is_async.iter().enumerate() {
+        let call = match (asynchronous, runtime.is_some()) {
+            (false, _) => format!("example_{}::run();", index + 1),
+            (true, true) => format!("example_{}::run().await;", index + 1),
+            (true, false) => format!(
+                "// example_{}::run() is async; call it from your runtime",
+                index + 1
+            ),
+        };
+        source.push_str(&format!("    {}\n", call));
+    }
+    source.push_str("}\n");
+    source
+}
+
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("    {}\n", line)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,242 @@
+use crate::commands::check_dep_names;
+use crate::{cratesio, manifest};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, value};
+
+/// `[workspace.package]` fields a spec may set under `"package"`.
+const SHARED_FIELDS: &[&str] = &[
+    "version",
+    "edition",
+    "rust-version",
+    "license",
+    "authors",
+    "repository",
+    "homepage",
+];
+
+/// One entry of the spec's `members` list.
+struct Member {
+    name: String,
+    is_bin: bool,
+    deps: Vec<String>,
+}
+
+pub fn run(spec_path: &str, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let spec: Value = serde_json::from_str(&fs::read_to_string(spec_path)?)
+        .map_err(|e| format!("{} is not valid JSON: {}", spec_path, e))?;
+    let members = parse_members(&spec)?;
+
+    if !dry_run && Path::new(manifest::MANIFEST_PATH).exists() {
+        return Err("Cargo.toml already exists; run this in an empty directory".into());
+    }
+    for member in &members {
+        if !dry_run && Path::new(&member.name).exists() {
+            return Err(format!("{}/ already exists", member.name).into());
+        }
+    }
+
+    // External dependencies are pinned to their latest version once, in
+    // [workspace.dependencies], and inherited by the members
+    let mut external: BTreeMap<String, String> = BTreeMap::new();
+    for dep in members.iter().flat_map(|member| &member.deps) {
+        if members.iter().any(|m| m.name == *dep) || external.contains_key(dep) {
+            continue;
+        }
+        let version =
+            resolve(dep).ok_or_else(|| format!("crate {} not found on crates.io", dep))?;
+        external.insert(dep.clone(), version);
+    }
+
+    let root = root_manifest(&spec, &members, &external);
+    let shared: Vec<&str> = root["workspace"]["package"]
+        .as_table_like()
+        .map(|package| package.iter().map(|(key, _)| key).collect())
+        .unwrap_or_default();
+    if dry_run {
+        println!("{}", root);
+        for member in &members {
+            println!(
+                "# {}/Cargo.toml\n{}",
+                member.name,
+                member_manifest(member, &shared)
+            );
+        }
+        return Ok(());
+    }
+
+    manifest::write_manifest(&root)?;
+    println!("✓ Wrote Cargo.toml with {} members", members.len());
+    for member in &members {
+        let src = Path::new(&member.name).join("src");
+        fs::create_dir_all(&src)?;
+        manifest::write_manifest_at(
+            &Path::new(&member.name).join("Cargo.toml"),
+            &member_manifest(member, &shared),
+        )?;
+        let (file, source) = if member.is_bin {
+            (
+                "main.rs",
+                format!(
+                    "fn main() {{\n    println!(\"Hello from {}!\");\n}}\n",
+                    member.name
+                ),
+            )
+        } else {
+            ("lib.rs", format!("//! The {} crate.\n", member.name))
+        };
+        fs::write(src.join(file), source)?;
+        println!(
+            "✓ Created {}/ ({})",
+            member.name,
+            if member.is_bin { "bin" } else { "lib" }
+        );
+    }
+    println!("\nRun `cargo check --workspace` to verify the layout.");
+
+    Ok(())
+}
+
+fn parse_members(spec: &Value) -> Result<Vec<Member>, Box<dyn std::error::Error>> {
+    let entries = spec["members"]
+        .as_array()
+        .ok_or("the spec needs a \"members\" array")?;
+
+    let mut members: Vec<Member> = Vec::new();
+    for entry in entries {
+        let name = entry["name"]
+            .as_str()
+            .ok_or("every member needs a \"name\"")?
+            .to_string();
+        let violations = check_dep_names::naming_violations(&name);
+        if !violations.is_empty() {
+            return Err(format!("invalid crate name {}: {}", name, violations.join("; ")).into());
+        }
+        if members.iter().any(|m| m.name == name) {
+            return Err(format!("member {} is listed twice", name).into());
+        }
+        let is_bin = match entry["type"].as_str().unwrap_or("lib") {
+            "lib" => false,
+            "bin" => true,
+            other => {
+                return Err(format!("member {} has type {}; use lib or bin", name, other).into());
+            }
+        };
+        let deps = entry["deps"]
+            .as_array()
+            .map(|deps| {
+                deps.iter()
+                    .filter_map(|dep| dep.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        members.push(Member { name, is_bin, deps });
+    }
+
+    for member in &members {
+        for dep in &member.deps {
+            if *dep == member.name {
+                return Err(format!("member {} depends on itself", member.name).into());
+            }
+            if members.iter().any(|m| m.name == *dep && m.is_bin) {
+                return Err(format!(
+                    "{} depends on {}, which is a bin; only lib members can be dependencies",
+                    member.name, dep
+                )
+                .into());
+            }
+        }
+    }
+
+    if members.is_empty() {
+        return Err("the spec lists no members".into());
+    }
+    Ok(members)
+}
+
+fn root_manifest(
+    spec: &Value,
+    members: &[Member],
+    external: &BTreeMap<String, String>,
+) -> DocumentMut {
+    let mut doc = DocumentMut::new();
+
+    let mut workspace = Table::new();
+    let mut names = Array::new();
+    for member in members {
+        names.push(member.name.as_str());
+    }
+    workspace.insert("members", value(names));
+    workspace.insert("resolver", value("3"));
+
+    let mut package = Table::new();
+    package.insert("version", value("0.1.0"));
+    package.insert("edition", value("2024"));
+    for field in SHARED_FIELDS {
+        match &spec["package"][field] {
+            Value::String(text) => {
+                package.insert(field, value(text.as_str()));
+            }
+            Value::Array(items) => {
+                let array: Array = items.iter().filter_map(Value::as_str).collect();
+                package.insert(field, value(array));
+            }
+            _ => {}
+        }
+    }
+
+    let mut dependencies = Table::new();
+    for member in members.iter().filter(|m| !m.is_bin) {
+        if members.iter().any(|m| m.deps.contains(&member.name)) {
+            let mut spec = InlineTable::new();
+            spec.insert("path", member.name.as_str().into());
+            dependencies.insert(&member.name, value(spec));
+        }
+    }
+    for (name, version) in external {
+        dependencies.insert(name, value(version.as_str()));
+    }
+
+    // Dotted headers: [workspace], [workspace.package], [workspace.dependencies]
+    workspace.insert("package", Item::Table(package));
+    if !dependencies.is_empty() {
+        workspace.insert("dependencies", Item::Table(dependencies));
+    }
+    doc.insert("workspace", Item::Table(workspace));
+    doc
+}
+
+/// A member manifest that inherits every `[workspace.package]` field.
+fn member_manifest(member: &Member, shared: &[&str]) -> DocumentMut {
+    let mut doc = DocumentMut::new();
+
+    let mut package = Table::new();
+    package.insert("name", value(member.name.as_str()));
+    for field in shared {
+        package.insert(field, inherited());
+    }
+    doc.insert("package", Item::Table(package));
+
+    let mut dependencies = Table::new();
+    for dep in &member.deps {
+        dependencies.insert(dep, inherited());
+    }
+    doc.insert("dependencies", Item::Table(dependencies));
+    doc
+}
+
+/// `{ workspace = true }`, which takes the value from the workspace root.
+fn inherited() -> Item {
+    let mut table = InlineTable::new();
+    table.insert("workspace", true.into());
+    value(table)
+}
+
+/// Latest stable version of a crate on crates.io, without build metadata.
+fn resolve(name: &str) -> Option<String> {
+    let (_, entries) = cratesio::find_crate(name)?;
+    let version = cratesio::latest_version(&entries)?;
+    Some(version.split('+').next()?.to_string())
+}
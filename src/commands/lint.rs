@@ -0,0 +1,28 @@
+use crate::commands::{check_dep_names, check_feature_docs, check_feature_flags};
+
+type Lint = fn() -> Result<(), Box<dyn std::error::Error>>;
+
+/// Checks that only read Cargo.toml, so they're fast enough to run together.
+const LINTS: &[(&str, Lint)] = &[
+    ("check-dep-names", check_dep_names::run),
+    ("check-feature-flags", check_feature_flags::run),
+    ("check-feature-docs", check_feature_docs::run),
+];
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut failed = Vec::new();
+    for (name, lint) in LINTS {
+        println!("== {} ==", name);
+        if let Err(e) = lint() {
+            println!("✗ {}", e);
+            failed.push(*name);
+        }
+        println!();
+    }
+
+    if failed.is_empty() {
+        println!("✓ All {} lints passed.", LINTS.len());
+        return Ok(());
+    }
+    Err(format!("failed lints: {}", failed.join(", ")).into())
+}
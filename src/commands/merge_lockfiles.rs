@@ -0,0 +1,132 @@
+use crate::commands::confirm;
+use crate::lockfile::{self, LOCKFILE_PATH, LockedPackage};
+use crate::metadata;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&["--no-deps"])?;
+    let root = PathBuf::from(metadata["workspace_root"].as_str().unwrap_or("."));
+
+    let root_lockfile = root.join(LOCKFILE_PATH);
+    if !root_lockfile.exists() {
+        return Err("workspace root has no Cargo.lock; run `cargo generate-lockfile` first".into());
+    }
+    let root_packages = lockfile::read_lockfile(&root_lockfile)?;
+
+    let member_lockfiles: Vec<PathBuf> = metadata::packages(&metadata)
+        .iter()
+        .filter_map(|package| package["manifest_path"].as_str())
+        .filter_map(|manifest| Path::new(manifest).parent().map(Path::to_path_buf))
+        .filter(|dir| *dir != root)
+        .map(|dir| dir.join(LOCKFILE_PATH))
+        .filter(|path| path.exists())
+        .collect();
+
+    if member_lockfiles.is_empty() {
+        println!("✓ No member-level Cargo.lock files found.");
+    }
+
+    for member_lockfile in &member_lockfiles {
+        println!("Found {}", member_lockfile.display());
+        let member_packages = lockfile::read_lockfile(member_lockfile)?;
+        let conflicts = conflicting_versions(&member_packages, &root_packages);
+
+        if conflicts.is_empty() {
+            println!("✓ Consistent with the workspace lockfile");
+        } else {
+            println!(
+                "✗ Differs from the workspace lockfile (the root lockfile is what cargo uses):"
+            );
+            for conflict in &conflicts {
+                println!("  - {}", conflict);
+            }
+        }
+
+        if !confirm(&format!("Remove {}?", member_lockfile.display())) {
+            continue;
+        }
+        fs::remove_file(member_lockfile)?;
+        println!("✓ Removed {}", member_lockfile.display());
+
+        if let Some(member_dir) = member_lockfile.parent() {
+            ignore_lockfile(member_dir)?;
+        }
+    }
+
+    check_root_lockfile_committed(&root);
+
+    Ok(())
+}
+
+fn conflicting_versions(member: &[LockedPackage], root: &[LockedPackage]) -> Vec<String> {
+    member
+        .iter()
+        .filter_map(|package| {
+            let root_versions: Vec<&str> = root
+                .iter()
+                .filter(|p| p.name == package.name)
+                .map(|p| p.version.as_str())
+                .collect();
+
+            if root_versions.contains(&package.version.as_str()) {
+                None
+            } else if root_versions.is_empty() {
+                Some(format!(
+                    "{} {} is not in the root lockfile",
+                    package.name, package.version
+                ))
+            } else {
+                Some(format!(
+                    "{} {} (root has {})",
+                    package.name,
+                    package.version,
+                    root_versions.join(", ")
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Adds `Cargo.lock` to the member's `.gitignore` so it isn't recreated in git.
+fn ignore_lockfile(member_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let gitignore = member_dir.join(".gitignore");
+    let content = fs::read_to_string(&gitignore).unwrap_or_default();
+    if content
+        .lines()
+        .any(|line| line.trim().trim_start_matches('/') == LOCKFILE_PATH)
+    {
+        return Ok(());
+    }
+
+    let mut updated = content;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(&format!("/{}\n", LOCKFILE_PATH));
+    fs::write(&gitignore, updated)?;
+    println!("✓ Added Cargo.lock to {}", gitignore.display());
+    Ok(())
+}
+
+fn check_root_lockfile_committed(root: &Path) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(root)
+            .args(args)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    };
+
+    if git(&["check-ignore", "-q", LOCKFILE_PATH]) {
+        println!(
+            "✗ The workspace Cargo.lock is ignored by git; remove it from .gitignore and commit it."
+        );
+    } else if !git(&["ls-files", "--error-unmatch", LOCKFILE_PATH]) {
+        println!("✗ The workspace Cargo.lock is not committed; run `git add Cargo.lock`.");
+    } else {
+        println!("✓ The workspace Cargo.lock is committed.");
+    }
+}
@@ -0,0 +1,202 @@
+pub mod audit_trail;
+pub mod check_arch;
+pub mod check_async;
+pub mod check_bin;
+pub mod check_binary_features;
+pub mod check_categories;
+pub mod check_cfg;
+pub mod check_clippy_deps;
+pub mod check_compile_time_budget;
+pub mod check_cross;
+pub mod check_default_features;
+pub mod check_dep_cycles_features;
+pub mod check_dep_graph;
+pub mod check_dep_names;
+pub mod check_dev_boundary;
+pub mod check_doc_links;
+pub mod check_doc_test;
+pub mod check_docs;
+pub mod check_edition_2024;
+pub mod check_embedded;
+pub mod check_entrypoint;
+pub mod check_examples_isolation;
+pub mod check_feature_docs;
+pub mod check_feature_flags;
+pub mod check_feature_matrix;
+pub mod check_fuzz;
+pub mod check_git_dirty;
+pub mod check_global_alloc;
+pub mod check_inline_hints;
+pub mod check_io_uring;
+pub mod check_link_flags;
+pub mod check_lockfile_audit;
+pub mod check_lockfile_v2;
+pub mod check_macro_exports;
+pub mod check_namespace;
+pub mod check_nightly;
+pub mod check_override;
+pub mod check_panic;
+pub mod check_platform_deps;
+pub mod check_proc_macro_hygiene;
+pub mod check_publish;
+pub mod check_repository_url;
+pub mod check_resolver;
+pub mod check_rpc;
+pub mod check_semver_strict;
+pub mod check_size_budget;
+pub mod check_sysroot;
+pub mod check_test_attributes;
+pub mod check_test_compilation;
+pub mod check_unsafe_deps;
+pub mod check_version_age;
+pub mod check_wasm;
+pub mod check_workspace_authors;
+pub mod check_workspace_edition;
+pub mod check_workspace_root;
+pub mod check_yanked_lockfile;
+pub mod clean_git_deps;
+pub mod generate_badge;
+pub mod generate_ci_matrix;
+pub mod generate_complete_toml;
+pub mod generate_examples;
+pub mod generate_workspace_toml;
+pub mod lint;
+pub mod merge_lockfiles;
+pub mod pin_all_git;
+pub mod reconcile;
+pub mod suggest;
+pub mod visualize_deps;
+
+use crate::extract_missing_crates;
+use std::collections::VecDeque;
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Default cap on the feature combinations compiled by check-binary-features
+/// and check-feature-matrix.
+pub const MAX_FEATURE_COMBINATIONS: usize = 16;
+
+/// Asks a yes/no question on stdin. Anything other than "y"/"yes" is a no.
+pub fn confirm(prompt: &str) -> bool {
+    print!("{} [y/N] ", prompt);
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Location of cargo's home directory (`$CARGO_HOME`, falling back to `~/.cargo`).
+pub fn cargo_home() -> PathBuf {
+    if let Ok(home) = env::var("CARGO_HOME") {
+        return PathBuf::from(home);
+    }
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".cargo")
+}
+
+/// Compilation targets added with `rustup target add`. Empty when rustup
+/// isn't available.
+pub fn installed_targets() -> Vec<String> {
+    Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Runs `cargo check` for a single target (`--bin <name>`, `--example <name>`, ...).
+/// Returns `None` if it compiles, otherwise the missing crates found in the errors.
+pub fn check_target(
+    flag: &str,
+    name: &str,
+) -> Result<Option<Vec<String>>, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo").args(["check", flag, name]).output()?;
+
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(Some(extract_missing_crates(&stderr)))
+}
+
+/// Formats crate names as a comma-separated list of `code` spans.
+pub fn backticked(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Human-readable size in KB or MB.
+pub fn format_size(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Combinations of `features`, breadth-first through the power set: every
+/// combination of n features comes before any of n + 1, so a cap of `max`
+/// cuts off the largest ones first. `None` returns the whole power set.
+pub fn feature_combinations(features: &[String], max: Option<usize>) -> Vec<Vec<String>> {
+    let mut queue: VecDeque<Vec<usize>> = VecDeque::from([Vec::new()]);
+    let mut combinations = Vec::new();
+
+    while let Some(combination) = queue.pop_front() {
+        if max.is_some_and(|max| combinations.len() >= max) {
+            break;
+        }
+        let next = combination.last().map_or(0, |&last| last + 1);
+        for i in next..features.len() {
+            let mut child = combination.clone();
+            child.push(i);
+            queue.push_back(child);
+        }
+        combinations.push(combination.iter().map(|&i| features[i].clone()).collect());
+    }
+
+    combinations
+}
+
+/// Runs `cargo check` with exactly `features` enabled. Returns the first error
+/// line when it fails.
+pub fn check_features(features: &[&str]) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args([
+            "check",
+            "--all-targets",
+            "--no-default-features",
+            "--features",
+        ])
+        .arg(features.join(","))
+        .output()?;
+
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error = stderr
+        .lines()
+        .find(|line| line.starts_with("error"))
+        .unwrap_or("compilation failed")
+        .to_string();
+    Ok(Some(error))
+}
@@ -0,0 +1,72 @@
+use crate::manifest;
+use std::process::Command;
+use toml_edit::value;
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let mut doc = manifest::read_manifest()?;
+    let mut floating = 0;
+    let mut pinned = 0;
+
+    for table in manifest::dependency_tables_mut(&mut doc) {
+        for (key, item) in table.iter_mut() {
+            let Some(spec) = item.as_table_like_mut() else {
+                continue;
+            };
+            let Some(url) = spec.get("git").and_then(|git| git.as_str()) else {
+                continue;
+            };
+            if spec.contains_key("rev") || spec.contains_key("tag") {
+                continue;
+            }
+
+            floating += 1;
+            let url = url.to_string();
+            let reference = match spec.get("branch").and_then(|branch| branch.as_str()) {
+                Some(branch) => format!("refs/heads/{}", branch),
+                None => "HEAD".to_string(),
+            };
+
+            println!("Resolving {} ({} {})...", key.get(), url, reference);
+            match resolve_commit(&url, &reference) {
+                Ok(rev) => {
+                    spec.remove("branch");
+                    spec.insert("rev", value(&rev));
+                    pinned += 1;
+                    println!("✓ Pinned {} to {}", key.get(), rev);
+                }
+                Err(e) => println!("✗ Could not resolve {}: {}", key.get(), e),
+            }
+        }
+    }
+
+    if floating == 0 {
+        println!("No floating git dependencies found.");
+        return Ok(());
+    }
+    if pinned == 0 {
+        return Err("none of the git dependencies could be resolved".into());
+    }
+
+    manifest::write_manifest(&doc)?;
+    println!("\nPinned {} git dependencies in Cargo.toml.", pinned);
+    Ok(())
+}
+
+/// Looks up the commit a remote ref points at without cloning the repository.
+fn resolve_commit(url: &str, reference: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("git")
+        .args(["ls-remote", url, reference])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim().to_string().into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| format!("ref {} not found", reference).into())
+}
@@ -0,0 +1,120 @@
+use crate::lockfile::{self, LOCKFILE_PATH, LockedPackage};
+use crate::{manifest, metadata};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+
+/// A locked version that has to change to satisfy Cargo.toml again.
+struct Change {
+    name: String,
+    from: String,
+    to: String,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    if !Path::new(LOCKFILE_PATH).exists() {
+        return Err("no Cargo.lock found; run `cargo generate-lockfile` first".into());
+    }
+
+    // --frozen refuses to touch Cargo.lock, so it fails exactly when the
+    // lockfile no longer matches the manifest.
+    let Err(error) = metadata::cargo_metadata(&["--frozen"]) else {
+        println!("✓ Cargo.lock is consistent with Cargo.toml.");
+        return Ok(());
+    };
+    println!("Cargo.lock is out of date with Cargo.toml:");
+    let error = error.to_string();
+    let reason = error
+        .lines()
+        .find_map(|line| line.split_once("error: ").map(|(_, reason)| reason))
+        .unwrap_or(&error);
+    println!("  {}\n", reason);
+
+    let before = lockfile::read_lockfile(Path::new(LOCKFILE_PATH))?;
+
+    // Let cargo resolve once on a throwaway lockfile to learn which locked
+    // versions have to move, then apply only those updates.
+    let after = manifest::with_restored_manifest(|| {
+        metadata::cargo_metadata(&[])?;
+        lockfile::read_lockfile(Path::new(LOCKFILE_PATH))
+    })?;
+
+    let changes = changes(&before, &after);
+    if changes.is_empty() {
+        println!("No locked versions need to change; running `cargo update --workspace`...");
+        update(&["--workspace"])?;
+        return verify();
+    }
+
+    println!("Reconciling {} crate(s):", changes.len());
+    for change in &changes {
+        println!("  - {} {} -> {}", change.name, change.from, change.to);
+    }
+    println!();
+
+    for change in &changes {
+        let spec = format!("{}@{}", change.name, change.from);
+        match update(&["-p", &spec, "--precise", &change.to]) {
+            Ok(()) => println!("✓ Updated {} to {}", change.name, change.to),
+            Err(e) => println!("✗ Could not update {}: {}", change.name, e),
+        }
+    }
+
+    if metadata::cargo_metadata(&["--frozen"]).is_err() {
+        // Packages added to or removed from the graph aren't covered by
+        // --precise updates; a workspace update picks them up without
+        // touching other locked versions.
+        update(&["--workspace"])?;
+    }
+    verify()
+}
+
+/// Re-runs the `--frozen` check so success is only claimed once cargo agrees.
+fn verify() -> Result<(), Box<dyn std::error::Error>> {
+    match metadata::cargo_metadata(&["--frozen"]) {
+        Ok(_) => {
+            println!("\n✓ Cargo.lock is consistent with Cargo.toml.");
+            Ok(())
+        }
+        Err(e) => Err(format!("Cargo.lock is still out of date after reconciling: {}", e).into()),
+    }
+}
+
+/// Versions that disappear from the lockfile, paired with the version of the
+/// same crate that replaces them.
+fn changes(before: &[LockedPackage], after: &[LockedPackage]) -> Vec<Change> {
+    let versions = |packages: &[LockedPackage], name: &str| -> BTreeSet<String> {
+        packages
+            .iter()
+            .filter(|package| package.name == name)
+            .map(|package| package.version.clone())
+            .collect()
+    };
+
+    let names: BTreeSet<&str> = before.iter().map(|package| package.name.as_str()).collect();
+    let mut changes = Vec::new();
+    for name in names {
+        let old = versions(before, name);
+        let new = versions(after, name);
+        let removed: Vec<&String> = old.difference(&new).collect();
+        let added: Vec<&String> = new.difference(&old).collect();
+
+        for (from, to) in removed.iter().zip(added.iter()) {
+            changes.push(Change {
+                name: name.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+    }
+    changes
+}
+
+fn update(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new("cargo").arg("update").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim().to_string().into());
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::commands::confirm;
+use crate::{install_crates, manifest};
+
+/// Built-in recommendation graph: when the first crate is a dependency but the
+/// second isn't, suggest the second for the given reason.
+const RECOMMENDATIONS: &[(&str, &str, &str)] = &[
+    ("serde", "serde_json", "JSON support"),
+    ("serde", "toml", "TOML config files"),
+    ("tokio", "tracing", "async-aware logging"),
+    ("tokio", "futures", "stream and future combinators"),
+    (
+        "tracing",
+        "tracing-subscriber",
+        "collecting and printing tracing spans",
+    ),
+    (
+        "log",
+        "env_logger",
+        "a logger implementation controlled by RUST_LOG",
+    ),
+    ("clap", "anyhow", "ergonomic error handling in binaries"),
+    ("reqwest", "serde_json", "decoding JSON response bodies"),
+    ("axum", "tower-http", "common HTTP middleware"),
+    ("axum", "tokio", "the async runtime axum is built on"),
+    ("actix-web", "actix-rt", "the actix runtime for tests"),
+    ("sqlx", "dotenvy", "loading DATABASE_URL from .env files"),
+    ("diesel", "dotenvy", "loading DATABASE_URL from .env files"),
+    ("thiserror", "anyhow", "application-level error context"),
+    ("chrono", "chrono-tz", "time zone support"),
+    ("rand", "rand_chacha", "a seedable, reproducible RNG"),
+    (
+        "criterion",
+        "criterion-macro",
+        "#[criterion] benchmark attributes",
+    ),
+    ("wasm-bindgen", "web-sys", "browser API bindings"),
+    (
+        "wasm-bindgen",
+        "js-sys",
+        "JavaScript standard library bindings",
+    ),
+    ("tonic", "prost", "protobuf message types"),
+];
+
+pub fn run(apply: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = manifest::read_manifest()?;
+    let dependencies = manifest::direct_dependencies(&doc);
+
+    let has = |name: &str| dependencies.iter().any(|dep| dep == name);
+
+    let mut suggestions: Vec<(&str, &str)> = Vec::new();
+    for (present, suggested, reason) in RECOMMENDATIONS {
+        if has(present) && !has(suggested) && !suggestions.iter().any(|(s, _)| s == suggested) {
+            suggestions.push((suggested, reason));
+        }
+    }
+
+    if suggestions.is_empty() {
+        println!("No suggestions: your dependencies already cover the common pairings.");
+        return Ok(());
+    }
+
+    println!("Suggested crates:");
+    for (suggested, reason) in &suggestions {
+        println!("  - Consider adding {} for {}", suggested, reason);
+    }
+
+    if apply {
+        println!();
+        if confirm("Install the suggested crates?") {
+            let crates: Vec<String> = suggestions.iter().map(|(s, _)| s.to_string()).collect();
+            install_crates(&crates);
+        }
+    } else {
+        println!("\nRun `cargo tidy suggest --apply` to install them.");
+    }
+
+    Ok(())
+}
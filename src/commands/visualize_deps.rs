@@ -0,0 +1,353 @@
+use crate::metadata;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+struct Package {
+    name: String,
+    version: String,
+    license: String,
+    description: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Filter {
+    All,
+    Direct,
+    Normal,
+    Dev,
+    Build,
+}
+
+impl Filter {
+    fn next(self) -> Filter {
+        match self {
+            Filter::All => Filter::Direct,
+            Filter::Direct => Filter::Normal,
+            Filter::Normal => Filter::Dev,
+            Filter::Dev => Filter::Build,
+            Filter::Build => Filter::All,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Filter::All => "all",
+            Filter::Direct => "direct only",
+            Filter::Normal => "normal",
+            Filter::Dev => "dev",
+            Filter::Build => "build",
+        }
+    }
+
+    fn allows(self, kind: &str) -> bool {
+        match self {
+            Filter::All | Filter::Direct => true,
+            Filter::Normal => kind == "normal",
+            Filter::Dev => kind == "dev",
+            Filter::Build => kind == "build",
+        }
+    }
+}
+
+struct Graph {
+    roots: Vec<String>,
+    packages: HashMap<String, Package>,
+    /// Package id -> (dependency id, dependency kind)
+    edges: HashMap<String, Vec<(String, String)>>,
+}
+
+/// One visible line of the tree. `path` is the chain of package ids from a
+/// root, so the same crate can be expanded independently in different places.
+struct Row {
+    path: Vec<String>,
+    kind: String,
+    has_children: bool,
+}
+
+struct App {
+    graph: Graph,
+    expanded: HashSet<Vec<String>>,
+    filter: Filter,
+    selected: usize,
+    scroll: usize,
+    details: Option<String>,
+}
+
+pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let metadata = metadata::cargo_metadata(&[])?;
+    let graph = build_graph(&metadata);
+    if graph.roots.is_empty() {
+        return Err("no workspace packages found".into());
+    }
+
+    let expanded = graph.roots.iter().map(|root| vec![root.clone()]).collect();
+    let mut app = App {
+        graph,
+        expanded,
+        filter: Filter::All,
+        selected: 0,
+        scroll: 0,
+        details: None,
+    };
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
+
+    let result = event_loop(&mut app, &mut stdout);
+
+    execute!(stdout, cursor::Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn event_loop(app: &mut App, stdout: &mut io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        let rows = visible_rows(app);
+        app.selected = app.selected.min(rows.len().saturating_sub(1));
+        draw(app, &rows, stdout)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up => app.selected = app.selected.saturating_sub(1),
+            KeyCode::Down if app.selected + 1 < rows.len() => app.selected += 1,
+            KeyCode::Right | KeyCode::Char(' ') => {
+                if let Some(row) = rows.get(app.selected)
+                    && !app.expanded.remove(&row.path)
+                    && row.has_children
+                {
+                    app.expanded.insert(row.path.clone());
+                }
+            }
+            KeyCode::Left => {
+                if let Some(row) = rows.get(app.selected)
+                    && !app.expanded.remove(&row.path)
+                    && row.path.len() > 1
+                {
+                    // Jump to and collapse the parent
+                    let parent = row.path[..row.path.len() - 1].to_vec();
+                    app.selected = rows.iter().position(|r| r.path == parent).unwrap_or(0);
+                    app.expanded.remove(&parent);
+                }
+            }
+            KeyCode::Enter => {
+                app.details = rows
+                    .get(app.selected)
+                    .and_then(|row| row.path.last())
+                    .and_then(|id| app.graph.packages.get(id))
+                    .map(|package| {
+                        format!(
+                            "{} {} | license: {} | {}",
+                            package.name, package.version, package.license, package.description
+                        )
+                    });
+            }
+            KeyCode::Char('f') => {
+                app.filter = app.filter.next();
+                app.selected = 0;
+                app.scroll = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(app: &mut App, rows: &[Row], stdout: &mut io::Stdout) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let width = width as usize;
+    // Header, details panel and help line take three lines
+    let list_height = (height as usize).saturating_sub(3).max(1);
+
+    if app.selected < app.scroll {
+        app.scroll = app.selected;
+    } else if app.selected >= app.scroll + list_height {
+        app.scroll = app.selected + 1 - list_height;
+    }
+
+    queue!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+    let header = format!("Dependency tree (filter: {})", app.filter.label());
+    queue!(stdout, Print(truncate(&header, width)))?;
+
+    for (line, row) in rows.iter().enumerate().skip(app.scroll).take(list_height) {
+        let id = row.path.last().map(String::as_str).unwrap_or_default();
+        let package = app.graph.packages.get(id);
+        let marker = if !row.has_children {
+            " "
+        } else if app.expanded.contains(&row.path) {
+            "▾"
+        } else {
+            "▸"
+        };
+        let kind = if row.kind == "normal" {
+            String::new()
+        } else {
+            format!(" [{}]", row.kind)
+        };
+        let text = format!(
+            "{}{} {} {}{}",
+            "  ".repeat(row.path.len() - 1),
+            marker,
+            package.map(|p| p.name.as_str()).unwrap_or(id),
+            package.map(|p| p.version.as_str()).unwrap_or_default(),
+            kind
+        );
+
+        queue!(stdout, cursor::MoveTo(0, (line - app.scroll + 1) as u16))?;
+        if line == app.selected {
+            queue!(
+                stdout,
+                SetAttribute(Attribute::Reverse),
+                Print(truncate(&text, width)),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(stdout, Print(truncate(&text, width)))?;
+        }
+    }
+
+    let details = app
+        .details
+        .as_deref()
+        .unwrap_or("Press Enter to show crate metadata");
+    queue!(
+        stdout,
+        cursor::MoveTo(0, height.saturating_sub(2)),
+        Print(truncate(details, width)),
+        cursor::MoveTo(0, height.saturating_sub(1)),
+        Print(truncate(
+            "↑/↓ move  →/space expand  ← collapse  enter details  f filter  q quit",
+            width
+        ))
+    )?;
+
+    stdout.flush()
+}
+
+fn visible_rows(app: &App) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for root in &app.graph.roots {
+        push_rows(app, vec![root.clone()], "normal".to_string(), &mut rows);
+    }
+    rows
+}
+
+fn push_rows(app: &App, path: Vec<String>, kind: String, rows: &mut Vec<Row>) {
+    let children = children(app, &path);
+    let expanded = app.expanded.contains(&path);
+    rows.push(Row {
+        path: path.clone(),
+        kind,
+        has_children: !children.is_empty(),
+    });
+
+    if expanded {
+        for (child, kind) in children {
+            let mut child_path = path.clone();
+            child_path.push(child);
+            push_rows(app, child_path, kind, rows);
+        }
+    }
+}
+
+/// Dependencies shown under the last package in `path`, after filtering.
+/// Packages already on the path are skipped so dev-dependency cycles terminate.
+fn children(app: &App, path: &[String]) -> Vec<(String, String)> {
+    if app.filter == Filter::Direct && path.len() > 1 {
+        return Vec::new();
+    }
+
+    let id = path.last().map(String::as_str).unwrap_or_default();
+    app.graph
+        .edges
+        .get(id)
+        .map(|edges| {
+            edges
+                .iter()
+                .filter(|(child, kind)| app.filter.allows(kind) && !path.contains(child))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn build_graph(metadata: &Value) -> Graph {
+    let packages = metadata::packages(metadata)
+        .iter()
+        .filter_map(|package| {
+            let id = package["id"].as_str()?.to_string();
+            let field = |key: &str| package[key].as_str().unwrap_or("-").to_string();
+            Some((
+                id,
+                Package {
+                    name: field("name"),
+                    version: field("version"),
+                    license: field("license"),
+                    description: field("description").replace('\n', " "),
+                },
+            ))
+        })
+        .collect();
+
+    let mut edges: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for node in metadata["resolve"]["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let Some(id) = node["id"].as_str() else {
+            continue;
+        };
+        for dep in node["deps"].as_array().into_iter().flatten() {
+            let Some(pkg) = dep["pkg"].as_str() else {
+                continue;
+            };
+            let mut kinds: Vec<String> = dep["dep_kinds"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|k| k["kind"].as_str().unwrap_or("normal").to_string())
+                .collect();
+            kinds.sort();
+            kinds.dedup();
+            for kind in kinds {
+                edges
+                    .entry(id.to_string())
+                    .or_default()
+                    .push((pkg.to_string(), kind));
+            }
+        }
+    }
+
+    let roots = metadata["workspace_members"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str().map(str::to_string))
+        .collect();
+
+    Graph {
+        roots,
+        packages,
+        edges,
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
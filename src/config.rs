@@ -0,0 +1,13 @@
+use std::fs;
+use toml_edit::DocumentMut;
+
+pub const CONFIG_PATH: &str = ".cargo-tidy.toml";
+
+/// Reads the project's `.cargo-tidy.toml`. A missing file is an empty config.
+pub fn read_config() -> Result<DocumentMut, Box<dyn std::error::Error>> {
+    match fs::read_to_string(CONFIG_PATH) {
+        Ok(content) => Ok(content.parse::<DocumentMut>()?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DocumentMut::new()),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,283 @@
+use crate::commands;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// crates.io rejects API requests without a descriptive user agent.
+const USER_AGENT: &str = concat!(
+    "cargo-tidy/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/Zachdehooge/cargo-tidy)"
+);
+
+/// Fetches a URL with curl and parses the body as JSON.
+pub fn fetch_json(url: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let output = Command::new("curl")
+        .args(["-sSfL", "-A", USER_AGENT, url])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("request to {} failed: {}", url, stderr.trim()).into());
+    }
+
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// Status code of an HTTP HEAD request to `url`, after following redirects.
+pub fn head_status(url: &str) -> Result<u16, Box<dyn std::error::Error>> {
+    let output = Command::new("curl")
+        .args([
+            "-sSIL",
+            "-o",
+            "/dev/null",
+            "-w",
+            "%{http_code}",
+            "-A",
+            USER_AGENT,
+            url,
+        ])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("request to {} failed: {}", url, stderr.trim()).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().parse()?)
+}
+
+/// crates.io's crawler policy allows one API request per second.
+const API_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When the last API request was sent, shared by every caller.
+static LAST_API_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Crate metadata and the full version list from `/api/v1/crates/<name>`.
+/// Calls are spaced at least [`API_INTERVAL`] apart.
+pub fn crate_info(name: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut last = LAST_API_REQUEST
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(wait) = last.and_then(|last| API_INTERVAL.checked_sub(last.elapsed())) {
+        thread::sleep(wait);
+    }
+    *last = Some(Instant::now());
+    drop(last);
+
+    fetch_json(&format!("https://crates.io/api/v1/crates/{}", name))
+}
+
+/// Every published version of a crate from the sparse index, one JSON object
+/// per line. Unlike the API this needs no rate limiting.
+pub fn index_entries(name: &str) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    Ok(parse_index(&fetch_index(name)?))
+}
+
+/// Like [`index_entries`], but reuses a copy downloaded less than `max_age`
+/// ago. The cache lives under the user's cargo home rather than the shared
+/// temp directory, where other users could plant entries.
+pub fn cached_index_entries(
+    name: &str,
+    max_age: Duration,
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let dir = commands::cargo_home().join("cargo-tidy").join("index");
+    let path = dir.join(name.to_lowercase());
+
+    let fresh = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < max_age);
+    if fresh && let Ok(body) = fs::read_to_string(&path) {
+        return Ok(parse_index(&body));
+    }
+
+    let body = fetch_index(name)?;
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, &body)?;
+    Ok(parse_index(&body))
+}
+
+fn fetch_index(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let name = name.to_lowercase();
+    let prefix = match name.len() {
+        1 => "1".to_string(),
+        2 => "2".to_string(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    };
+    let url = format!("https://index.crates.io/{}/{}", prefix, name);
+
+    let output = Command::new("curl")
+        .args(["-sSfL", "-A", USER_AGENT, &url])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("request to {} failed: {}", url, stderr.trim()).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn parse_index(body: &str) -> Vec<Value> {
+    body.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Looks a crate up by the name used in Rust paths, where hyphens become
+/// underscores. Returns the published name with its index entries.
+pub fn find_crate(name: &str) -> Option<(String, Vec<Value>)> {
+    let mut candidates = vec![name.to_string()];
+    if name.contains('_') {
+        candidates.push(name.replace('_', "-"));
+    }
+
+    candidates
+        .into_iter()
+        .find_map(|package| Some((package.clone(), index_entries(&package).ok()?)))
+}
+
+/// The highest stable, non-yanked version in the index entries of a crate.
+pub fn latest_version(entries: &[Value]) -> Option<String> {
+    entries
+        .iter()
+        .filter(|entry| !entry["yanked"].as_bool().unwrap_or(false))
+        .filter_map(|entry| entry["vers"].as_str())
+        .filter_map(|version| Some((version_key(version)?, version)))
+        .max()
+        .map(|(_, version)| version.to_string())
+}
+
+/// Numeric `(major, minor, patch)` of a stable version. Pre-releases and
+/// anything unparsable yield `None`.
+fn version_key(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.split('+').next()?;
+    if version.contains('-') {
+        return None;
+    }
+    let mut parts = version.split('.').map(|part| part.parse::<u64>());
+    Some((
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    ))
+}
+
+/// Whole days elapsed since an RFC 3339 timestamp such as
+/// `2023-01-15T12:34:56.789+00:00`. Only the date part is considered.
+pub fn days_since(timestamp: &str) -> Option<i64> {
+    let date = timestamp.get(..10)?;
+    let mut parts = date.split('-').map(|part| part.parse::<i64>());
+    let (year, month, day) = (
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    );
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    Some(now / 86_400 - days_from_civil(year, month, day))
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Downloads `name-version` from crates.io, checks it against the index
+/// checksum and unpacks it, returning the path of the extracted crate root.
+/// Like the index cache, the crates live under the user's cargo home: their
+/// build scripts get run, so nothing another user could write is trusted.
+pub fn download_crate(name: &str, version: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = commands::cargo_home().join("cargo-tidy").join("crates");
+    let root = dir.join(format!("{}-{}", name, version));
+    if root.join("Cargo.toml").exists() {
+        return Ok(root);
+    }
+    fs::create_dir_all(&dir)?;
+
+    let expected = index_entries(name)?
+        .into_iter()
+        .find(|entry| entry["vers"] == version)
+        .and_then(|entry| entry["cksum"].as_str().map(str::to_string))
+        .ok_or_else(|| format!("{} {} is not in the crates.io index", name, version))?;
+
+    let archive = dir.join(format!("{}-{}.crate", name, version));
+    let url = format!(
+        "https://static.crates.io/crates/{0}/{0}-{1}.crate",
+        name, version
+    );
+    let output = Command::new("curl")
+        .args(["-sSfL", "-A", USER_AGENT, "-o"])
+        .arg(&archive)
+        .arg(&url)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to download {}: {}", url, stderr.trim()).into());
+    }
+
+    let actual = sha256(&archive);
+    if actual.as_deref().ok() != Some(expected.as_str()) {
+        let _ = fs::remove_file(&archive);
+        return Err(match actual {
+            Ok(actual) => format!(
+                "checksum mismatch for {} {}: index has {}, download has {}",
+                name, version, expected, actual
+            )
+            .into(),
+            Err(e) => e,
+        });
+    }
+
+    // Unpack next to the cache so a failed extraction never looks complete
+    let staging = dir.join(format!(".{}-{}.partial", name, version));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)?;
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&staging)
+        .output()?;
+    let _ = fs::remove_file(&archive);
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&staging);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to unpack {}: {}", archive.display(), stderr.trim()).into());
+    }
+    let _ = fs::remove_dir_all(&root);
+    fs::rename(staging.join(format!("{}-{}", name, version)), &root)?;
+    let _ = fs::remove_dir_all(&staging);
+
+    Ok(root)
+}
+
+/// Hex SHA-256 of a file, from `sha256sum` or macOS's `shasum -a 256`.
+fn sha256(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("sha256sum").arg(path).output().or_else(|_| {
+        Command::new("shasum")
+            .args(["-a", "256"])
+            .arg(path)
+            .output()
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to hash {}: {}", path.display(), stderr.trim()).into());
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| format!("failed to hash {}", path.display()).into())
+}
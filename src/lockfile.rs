@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+pub const LOCKFILE_PATH: &str = "Cargo.lock";
+
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    /// SHA-256 of the downloaded `.crate` file, for registry packages
+    pub checksum: Option<String>,
+}
+
+impl LockedPackage {
+    /// Whether the package was downloaded from crates.io (as opposed to a
+    /// path, git or alternative registry source).
+    pub fn is_from_crates_io(&self) -> bool {
+        self.source.as_deref() == Some("registry+https://github.com/rust-lang/crates.io-index")
+            || self.source.as_deref() == Some("sparse+https://index.crates.io/")
+    }
+}
+
+/// Reads the `[[package]]` entries of a Cargo.lock file.
+pub fn read_lockfile(path: &Path) -> Result<Vec<LockedPackage>, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let doc = content.parse::<DocumentMut>()?;
+
+    let Some(entries) = doc.get("package").and_then(|p| p.as_array_of_tables()) else {
+        return Ok(Vec::new());
+    };
+
+    let packages = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(LockedPackage {
+                name: entry.get("name")?.as_str()?.to_string(),
+                version: entry.get("version")?.as_str()?.to_string(),
+                source: entry
+                    .get("source")
+                    .and_then(|s| s.as_str())
+                    .map(str::to_string),
+                checksum: entry
+                    .get("checksum")
+                    .and_then(|c| c.as_str())
+                    .map(str::to_string),
+            })
+        })
+        .collect();
+
+    Ok(packages)
+}
+
+/// The lockfile format version. v3 introduced the `version = N` header;
+/// without one, a `[metadata]` checksum table means v1 and its absence v2.
+pub fn lockfile_version(path: &Path) -> Result<i64, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let doc = content.parse::<DocumentMut>()?;
+
+    if let Some(version) = doc.get("version").and_then(|v| v.as_integer()) {
+        return Ok(version);
+    }
+    Ok(if doc.contains_key("metadata") { 1 } else { 2 })
+}
@@ -1,3 +1,12 @@
+mod commands;
+mod config;
+mod cratesio;
+mod lockfile;
+mod manifest;
+mod metadata;
+mod source;
+
+use clap::{Parser, Subcommand};
 use regex::Regex;
 use std::collections::HashSet;
 use std::env;
@@ -5,6 +14,245 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 
+#[derive(Parser)]
+#[command(
+    name = "cargo-tidy",
+    version,
+    about = "Detects undeclared or unused dependencies and keeps Cargo.toml tidy"
+)]
+struct Cli {
+    /// Only scan for std usage that would break a no_std build
+    #[arg(long)]
+    no_std_check: bool,
+
+    /// Don't warn about uncommitted changes before modifying the project
+    #[arg(long, global = true)]
+    allow_dirty: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Run cargo-semver-checks against pending dependency upgrades
+    CheckSemverStrict,
+    /// Recommend crates commonly paired with your existing dependencies
+    Suggest {
+        /// Install the suggestions after confirmation
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Check each binary target separately and report missing crates per binary
+    CheckBin,
+    /// Preview missing crates and errors that switching to edition 2024 would cause
+    #[command(name = "check-edition-2024")]
+    CheckEdition2024,
+    /// Report `use std::` and `extern crate std;` in no_std code
+    CheckSysroot,
+    /// Pin every git dependency without a `rev` to its current commit
+    PinAllGit,
+    /// Find intra-doc links that point at crates missing from dependencies
+    CheckDocLinks,
+    /// Find crates missing only when compiling tests and add them as dev-dependencies
+    CheckTestCompilation,
+    /// Report optional dependencies that no feature enables
+    CheckFeatureFlags,
+    /// List cached git dependency sources and remove ones this project no longer uses
+    CleanGitDeps {
+        /// Remove unused sources from the cargo git cache after confirmation
+        #[arg(long)]
+        unused: bool,
+    },
+    /// Report public API items that lack documentation
+    CheckDocs,
+    /// Remove member-level Cargo.lock files in favour of the workspace lockfile
+    MergeLockfiles,
+    /// Check each example separately and report the dev-dependencies it needs
+    CheckExamplesIsolation,
+    /// Report locked versions published longer ago than the allowed age
+    CheckVersionAge {
+        /// Maximum allowed age of a locked version, in days
+        #[arg(long, default_value_t = 548)]
+        max_age_days: i64,
+    },
+    /// Report unknown cfg flags and feature names that don't match [features]
+    CheckCfg,
+    /// Show the commit and author that first added each dependency
+    AuditTrail,
+    /// Fail if a release binary exceeds the size budget in .cargo-tidy.toml
+    CheckSizeBudget {
+        /// Estimate the size impact of adding this crate
+        #[arg(long, value_name = "CRATE")]
+        add: Option<String>,
+    },
+    /// Fail if a clean build exceeds the compile time budget in .cargo-tidy.toml
+    CheckCompileTimeBudget {
+        /// Estimate the compile time cost of adding this crate
+        #[arg(long, value_name = "CRATE")]
+        add: Option<String>,
+    },
+    /// Recommend profile overrides for crates that are slow in debug builds
+    CheckOverride {
+        /// Add the recommended overrides to Cargo.toml
+        #[arg(long)]
+        apply_recommended_overrides: bool,
+    },
+    /// Detect lockfiles older than format v3 and offer to upgrade them
+    #[command(name = "check-lockfile-v2")]
+    CheckLockfileV2,
+    /// Warn about target-specific dependencies that don't support their platform
+    CheckPlatformDeps,
+    /// Explore the dependency tree in an interactive terminal UI
+    VisualizeDeps,
+    /// Find crates used by doc tests that aren't dependencies
+    CheckDocTest,
+    /// Compare missing crates under feature resolver v1 and v2
+    CheckResolver,
+    /// Write a dependency health badge to dependency-badge.svg
+    GenerateBadge {
+        /// Print a shields.io badge URL instead of writing an SVG file
+        #[arg(long)]
+        shields_url: bool,
+    },
+    /// Compare stable and nightly builds to find nightly-only feature usage
+    CheckNightly,
+    /// Report workspace members whose edition differs from the majority
+    CheckWorkspaceEdition,
+    /// Verify crate names in Cargo.toml follow crates.io naming rules
+    CheckDepNames,
+    /// Fill in Cargo.toml dependencies for every external crate used in the source
+    GenerateCompleteToml {
+        /// Print the generated Cargo.toml instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compile every combination of [features] and report the ones that fail
+    CheckBinaryFeatures {
+        /// Maximum number of feature combinations to compile
+        #[arg(long, alias = "max-combos", default_value_t = commands::MAX_FEATURE_COMBINATIONS)]
+        max_feature_combinations: usize,
+    },
+    /// Report crates that clippy suggests while explaining lints
+    CheckClippyDeps,
+    /// Check the project for cross-compilation targets and report target-specific failures
+    CheckCross,
+    /// Suggest best practices for the workspace root manifest
+    CheckWorkspaceRoot,
+    /// Check that items re-exported from dependencies are inlined in docs and properly declared
+    CheckMacroExports,
+    /// Update only the locked versions that no longer satisfy Cargo.toml
+    Reconcile,
+    /// Warn about span hygiene anti-patterns in proc-macro crates
+    CheckProcMacroHygiene,
+    /// Report dependencies and APIs that don't work on wasm32-unknown-unknown
+    CheckWasm,
+    /// Check a bare-metal build and classify the crates that fail
+    CheckEmbedded {
+        /// Bare-metal target triple to check
+        #[arg(long, default_value = "thumbv7em-none-eabi")]
+        target: String,
+    },
+    /// Report dev-dependencies used outside test code in src/
+    CheckDevBoundary,
+    /// Create an example file from the code snippets in a crate's README and docs
+    GenerateExamples {
+        /// Crate whose examples to extract
+        #[arg(long = "crate")]
+        krate: String,
+    },
+    /// Check that the git working tree has no uncommitted changes
+    CheckGitDirty,
+    /// Detect cycles and incompatible diamond dependencies in the dependency graph
+    CheckDepGraph,
+    /// Report features in [features] without a description comment
+    CheckFeatureDocs,
+    /// Run the quick Cargo.toml lints: dep names, feature flags and feature docs
+    Lint,
+    /// Check every package in Cargo.lock for yanked versions
+    CheckYankedLockfile,
+    /// Report dependencies that shadow std modules or clash with workspace members
+    CheckNamespace,
+    /// Warn about dependencies that need native libraries installed on the system
+    CheckLinkFlags,
+    /// Report crates only used under target_arch cfgs that sit in [dependencies]
+    CheckArch,
+    /// Compile a matrix of feature combinations and find minimal failing sets
+    CheckFeatureMatrix {
+        /// Maximum number of feature combinations to compile
+        #[arg(long, alias = "max-feature-combinations", default_value_t = commands::MAX_FEATURE_COMBINATIONS)]
+        max_combos: usize,
+        /// Check every combination of features, ignoring --max-combos
+        #[arg(long)]
+        thorough: bool,
+    },
+    /// Check that io_uring crates are Linux-only dependencies with a documented kernel version
+    CheckIoUring,
+    /// Check that tonic/prost projects have their codegen crates and build.rs
+    CheckRpc,
+    /// Warn about mixed async runtimes and async-trait where native async traits work
+    CheckAsync,
+    /// Check that no_std binaries have a panic handler and abort on panic in release
+    CheckPanic,
+    /// Check that #[global_allocator] types come from declared dependencies
+    CheckGlobalAlloc,
+    /// Report dependencies with unsafe code that have no documented acceptance
+    CheckUnsafeDeps,
+    /// Check that the package is ready for `cargo publish` and report a readiness score
+    CheckPublish,
+    /// Check [package] categories against the crates.io category slugs
+    CheckCategories,
+    /// Check the format of the repository URL in [package]
+    CheckRepositoryUrl {
+        /// Also send an HTTP HEAD request to check that the repository exists
+        #[arg(long)]
+        check_exists: bool,
+    },
+    /// Report workspace members whose authors differ or aren't inherited from the workspace
+    CheckWorkspaceAuthors,
+    /// Run the benchmarks and suggest #[inline] for the dependency wrappers they time
+    CheckInlineHints,
+    /// Check test attributes in tests/ and src/: async tests, stray #[test] and unexplained #[ignore]
+    CheckTestAttributes,
+    /// Warn about dependencies whose default features are commonly disabled
+    CheckDefaultFeatures,
+    /// Check the cargo-fuzz setup in fuzz/: dependencies, workspace and .gitignore
+    CheckFuzz,
+    /// Create a workspace Cargo.toml and member skeletons from a JSON spec
+    GenerateWorkspaceToml {
+        /// JSON file listing the members, e.g. {"members": [{"name": "cli", "type": "bin"}]}
+        spec: String,
+        /// Print the generated manifests instead of writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare every Cargo.lock checksum with the one published on crates.io
+    #[command(alias = "verify-checksums")]
+    CheckLockfileAudit,
+    /// Check that binary entry points have a short main() that returns a Result
+    CheckEntrypoint {
+        /// Maximum number of lines in main() before suggesting a refactor
+        #[arg(long, default_value_t = 20)]
+        max_lines: usize,
+    },
+    /// Report dependency cycles that only appear when optional features are enabled
+    CheckDepCyclesFeatures,
+    /// Generate a CI workflow testing every platform, toolchain and feature set.
+    /// Stable, beta and nightly float; only the `rust-version` MSRV row is pinned
+    GenerateCiMatrix {
+        /// CI system to generate for: github, gitlab or circleci
+        #[arg(long, default_value = "github")]
+        provider: String,
+        /// Comma-separated features to test together; repeat for more sets.
+        /// `default`, `all` and `none` select cargo's own feature flags
+        #[arg(long, value_name = "FEATURES")]
+        matrix: Vec<String>,
+        /// Print the configuration instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 fn getos() -> String {
     env::consts::OS.to_string()
 }
@@ -36,6 +284,25 @@ fn find_missing_crates() {
         }
     }
 
+    match extract_attribute_macro_crates() {
+        Ok(candidates) => {
+            if !candidates.is_empty() {
+                println!("Likely dependencies from attribute macros (lower confidence):");
+                for candidate in &candidates {
+                    println!(
+                        "  - {} (#[{}])",
+                        candidate.crates.join(" or "),
+                        candidate.attribute
+                    );
+                }
+                println!("\nThese were not installed. To add one, run: cargo add <crate>\n");
+            }
+        }
+        Err(e) => {
+            eprintln!("Error reading source file: {}", e);
+        }
+    }
+
     match analyze_missing_crates() {
         Ok(crates) => {
             if !crates.is_empty() {
@@ -62,10 +329,23 @@ fn find_missing_crates() {
 }
 
 fn install_crates(crates: &[String]) {
+    add_crates(crates, &[]);
+}
+
+fn install_dev_crates(crates: &[String]) {
+    add_crates(crates, &["--dev"]);
+}
+
+fn add_crates(crates: &[String], extra_args: &[&str]) {
     for crate_name in crates {
         println!("Installing {}...", crate_name);
 
-        match Command::new("cargo").args(&["add", crate_name]).output() {
+        match Command::new("cargo")
+            .arg("add")
+            .args(extra_args)
+            .arg(crate_name)
+            .output()
+        {
             Ok(output) => {
                 if output.status.success() {
                     println!("✓ Successfully installed {}", crate_name);
@@ -106,10 +386,98 @@ fn extract_crates_from_source() -> Result<Vec<String>, Box<dyn std::error::Error
     Ok(result)
 }
 
+/// Attribute macros that are commonly used without a `use` statement, mapped to
+/// the crates that provide them.
+const ATTRIBUTE_MACRO_CRATES: &[(&str, &[&str])] = &[
+    ("get", &["rocket", "actix-web"]),
+    ("post", &["rocket", "actix-web"]),
+    ("put", &["rocket", "actix-web"]),
+    ("delete", &["rocket", "actix-web"]),
+    ("patch", &["rocket", "actix-web"]),
+    ("route", &["rocket", "actix-web"]),
+    ("launch", &["rocket"]),
+    ("catch", &["rocket"]),
+    ("handler", &["poem"]),
+    ("debug_handler", &["axum"]),
+    ("async_trait", &["async-trait"]),
+    ("instrument", &["tracing"]),
+    ("wasm_bindgen", &["wasm-bindgen"]),
+    ("pyfunction", &["pyo3"]),
+    ("pyclass", &["pyo3"]),
+    ("pymethods", &["pyo3"]),
+    ("pymodule", &["pyo3"]),
+    ("rstest", &["rstest"]),
+    ("test_case", &["test-case"]),
+    ("serial", &["serial_test"]),
+];
+
+/// Tool attributes that look like crate paths but aren't (`#[rustfmt::skip]`).
+const TOOL_ATTRIBUTES: &[&str] = &["rustfmt", "clippy", "rustdoc", "diagnostic"];
+
+struct MacroCandidate {
+    attribute: String,
+    crates: Vec<String>,
+}
+
+fn extract_attribute_macro_crates() -> Result<Vec<MacroCandidate>, Box<dyn std::error::Error>> {
+    let source_path = "src/main.rs";
+    let content = fs::read_to_string(source_path)?;
+
+    let dependencies = manifest::read_manifest()
+        .map(|doc| manifest::direct_dependencies(&doc))
+        .unwrap_or_default();
+    let is_declared = |name: &str| {
+        dependencies
+            .iter()
+            .any(|dep| dep.replace('-', "_") == name.replace('-', "_"))
+    };
+
+    let mut candidates: Vec<MacroCandidate> = Vec::new();
+
+    // Matches `#[name(...)]`, `#[name]` and `#[krate::name]`
+    let attribute_regex =
+        Regex::new(r"#\[\s*([a-zA-Z_][a-zA-Z0-9_]*)(::[a-zA-Z_][a-zA-Z0-9_:]*)?")?;
+
+    for cap in attribute_regex.captures_iter(&content) {
+        let first = &cap[1];
+        let crate_names: Vec<String> = if cap.get(2).is_some() {
+            // A path attribute names its crate directly, like `#[tokio::main]`
+            if is_std_module(first) || TOOL_ATTRIBUTES.contains(&first) {
+                continue;
+            }
+            vec![first.to_string()]
+        } else {
+            match ATTRIBUTE_MACRO_CRATES
+                .iter()
+                .find(|(name, _)| *name == first)
+            {
+                Some((_, crates)) => crates.iter().map(|c| c.to_string()).collect(),
+                None => continue,
+            }
+        };
+
+        let attribute = cap[0]
+            .trim_start_matches('#')
+            .trim_start_matches('[')
+            .trim();
+        if crate_names.iter().any(|name| is_declared(name))
+            || candidates.iter().any(|c| c.crates == crate_names)
+        {
+            continue;
+        }
+        candidates.push(MacroCandidate {
+            attribute: attribute.to_string(),
+            crates: crate_names,
+        });
+    }
+
+    Ok(candidates)
+}
+
 fn analyze_missing_crates() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     // Run cargo check to get compilation errors
     let output = Command::new("cargo")
-        .args(&["check", "--message-format=plain"])
+        .args(["check", "--message-format=plain"])
         .output()?;
 
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -147,6 +515,7 @@ fn extract_missing_crates(error_output: &str) -> Vec<String> {
 
     let patterns = vec![
         Regex::new(r"use of undeclared crate or module `([^`]+)`").unwrap(),
+        Regex::new(r"use of unresolved module or unlinked crate `([^`]+)`").unwrap(),
         Regex::new(r"failed to resolve: use of undeclared crate or module `([^`]+)`").unwrap(),
         Regex::new(r"unresolved import `([^`:]+)`").unwrap(),
         Regex::new(r"no external crate `([^`]+)`").unwrap(),
@@ -246,7 +615,7 @@ fn is_std_module(name: &str) -> bool {
 
 fn analyze_missing_crates_rustc() -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let output = Command::new("rustc")
-        .args(&["--error-format=human", "--crate-type=bin", "src/main.rs"])
+        .args(["--error-format=human", "--crate-type=bin", "src/main.rs"])
         .output()?;
 
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -264,7 +633,153 @@ fn analyze_missing_crates_rustc() -> Result<Vec<String>, Box<dyn std::error::Err
     Ok(missing_crates)
 }
 
+impl Commands {
+    /// Whether the command may change Cargo.toml, Cargo.lock or other files
+    /// in the project, which calls for a clean working tree first.
+    fn modifies_project(&self) -> bool {
+        match self {
+            Commands::Suggest { apply } => *apply,
+            Commands::CheckOverride {
+                apply_recommended_overrides,
+            } => *apply_recommended_overrides,
+            Commands::GenerateCompleteToml { dry_run } => !*dry_run,
+            Commands::GenerateWorkspaceToml { dry_run, .. } => !*dry_run,
+            Commands::GenerateCiMatrix { dry_run, .. } => !*dry_run,
+            Commands::GenerateBadge { shields_url } => !*shields_url,
+            Commands::CheckSemverStrict
+            | Commands::PinAllGit
+            | Commands::CheckTestCompilation
+            | Commands::MergeLockfiles
+            | Commands::CheckLockfileV2
+            | Commands::CheckWorkspaceEdition
+            | Commands::CheckArch
+            | Commands::CheckClippyDeps
+            | Commands::Reconcile
+            | Commands::CheckPanic
+            | Commands::CheckWorkspaceAuthors
+            | Commands::GenerateExamples { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+fn run_command(command: Commands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Commands::CheckSemverStrict => commands::check_semver_strict::run(),
+        Commands::Suggest { apply } => commands::suggest::run(apply),
+        Commands::CheckBin => commands::check_bin::run(),
+        Commands::CheckEdition2024 => commands::check_edition_2024::run(),
+        Commands::CheckSysroot => commands::check_sysroot::run(),
+        Commands::PinAllGit => commands::pin_all_git::run(),
+        Commands::CheckDocLinks => commands::check_doc_links::run(),
+        Commands::CheckTestCompilation => commands::check_test_compilation::run(),
+        Commands::CheckFeatureFlags => commands::check_feature_flags::run(),
+        Commands::CleanGitDeps { unused } => commands::clean_git_deps::run(unused),
+        Commands::CheckDocs => commands::check_docs::run(),
+        Commands::MergeLockfiles => commands::merge_lockfiles::run(),
+        Commands::CheckExamplesIsolation => commands::check_examples_isolation::run(),
+        Commands::CheckVersionAge { max_age_days } => {
+            commands::check_version_age::run(max_age_days)
+        }
+        Commands::CheckCfg => commands::check_cfg::run(),
+        Commands::AuditTrail => commands::audit_trail::run(),
+        Commands::CheckSizeBudget { add } => commands::check_size_budget::run(add),
+        Commands::CheckCompileTimeBudget { add } => commands::check_compile_time_budget::run(add),
+        Commands::CheckOverride {
+            apply_recommended_overrides,
+        } => commands::check_override::run(apply_recommended_overrides),
+        Commands::CheckLockfileV2 => commands::check_lockfile_v2::run(),
+        Commands::CheckPlatformDeps => commands::check_platform_deps::run(),
+        Commands::VisualizeDeps => commands::visualize_deps::run(),
+        Commands::CheckDocTest => commands::check_doc_test::run(),
+        Commands::CheckResolver => commands::check_resolver::run(),
+        Commands::GenerateBadge { shields_url } => commands::generate_badge::run(shields_url),
+        Commands::CheckNightly => commands::check_nightly::run(),
+        Commands::CheckWorkspaceEdition => commands::check_workspace_edition::run(),
+        Commands::CheckDepNames => commands::check_dep_names::run(),
+        Commands::GenerateCompleteToml { dry_run } => {
+            commands::generate_complete_toml::run(dry_run)
+        }
+        Commands::CheckBinaryFeatures {
+            max_feature_combinations,
+        } => commands::check_binary_features::run(max_feature_combinations),
+        Commands::CheckClippyDeps => commands::check_clippy_deps::run(),
+        Commands::CheckCross => commands::check_cross::run(),
+        Commands::CheckWorkspaceRoot => commands::check_workspace_root::run(),
+        Commands::CheckMacroExports => commands::check_macro_exports::run(),
+        Commands::Reconcile => commands::reconcile::run(),
+        Commands::CheckProcMacroHygiene => commands::check_proc_macro_hygiene::run(),
+        Commands::CheckWasm => commands::check_wasm::run(),
+        Commands::CheckEmbedded { target } => commands::check_embedded::run(&target),
+        Commands::CheckDevBoundary => commands::check_dev_boundary::run(),
+        Commands::GenerateExamples { krate } => commands::generate_examples::run(&krate),
+        Commands::CheckGitDirty => commands::check_git_dirty::run(),
+        Commands::CheckDepGraph => commands::check_dep_graph::run(),
+        Commands::CheckFeatureDocs => commands::check_feature_docs::run(),
+        Commands::Lint => commands::lint::run(),
+        Commands::CheckYankedLockfile => commands::check_yanked_lockfile::run(),
+        Commands::CheckNamespace => commands::check_namespace::run(),
+        Commands::CheckLinkFlags => commands::check_link_flags::run(),
+        Commands::CheckArch => commands::check_arch::run(),
+        Commands::CheckFeatureMatrix {
+            max_combos,
+            thorough,
+        } => commands::check_feature_matrix::run(max_combos, thorough),
+        Commands::CheckIoUring => commands::check_io_uring::run(),
+        Commands::CheckRpc => commands::check_rpc::run(),
+        Commands::CheckAsync => commands::check_async::run(),
+        Commands::CheckPanic => commands::check_panic::run(),
+        Commands::CheckGlobalAlloc => commands::check_global_alloc::run(),
+        Commands::CheckUnsafeDeps => commands::check_unsafe_deps::run(),
+        Commands::CheckPublish => commands::check_publish::run(),
+        Commands::CheckCategories => commands::check_categories::run(),
+        Commands::CheckRepositoryUrl { check_exists } => {
+            commands::check_repository_url::run(check_exists)
+        }
+        Commands::CheckWorkspaceAuthors => commands::check_workspace_authors::run(),
+        Commands::CheckInlineHints => commands::check_inline_hints::run(),
+        Commands::CheckTestAttributes => commands::check_test_attributes::run(),
+        Commands::CheckDefaultFeatures => commands::check_default_features::run(),
+        Commands::CheckFuzz => commands::check_fuzz::run(),
+        Commands::GenerateWorkspaceToml { spec, dry_run } => {
+            commands::generate_workspace_toml::run(&spec, dry_run)
+        }
+        Commands::CheckLockfileAudit => commands::check_lockfile_audit::run(),
+        Commands::CheckEntrypoint { max_lines } => commands::check_entrypoint::run(max_lines),
+        Commands::CheckDepCyclesFeatures => commands::check_dep_cycles_features::run(),
+        Commands::GenerateCiMatrix {
+            provider,
+            matrix,
+            dry_run,
+        } => commands::generate_ci_matrix::run(&provider, &matrix, dry_run),
+    }
+}
+
 fn main() {
+    // `cargo tidy ...` invokes this binary as `cargo-tidy tidy ...`
+    let mut args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("tidy") {
+        args.remove(1);
+    }
+    let cli = Cli::parse_from(args);
+
+    let command = cli
+        .command
+        .or(cli.no_std_check.then_some(Commands::CheckSysroot));
+    // Without a subcommand, missing crates are installed into Cargo.toml
+    let modifies_project = command.as_ref().is_none_or(Commands::modifies_project);
+    if modifies_project && !cli.allow_dirty {
+        commands::check_git_dirty::warn_if_dirty();
+    }
+
+    if let Some(command) = command {
+        if let Err(e) = run_command(command) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if getos() == "windows" {
         println!("PATH for {}: {}\\src\\main.rs", getos(), getdir());
         find_missing_crates();
@@ -0,0 +1,217 @@
+use crate::lockfile::LOCKFILE_PATH;
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, TableLike, value};
+
+pub const MANIFEST_PATH: &str = "Cargo.toml";
+
+pub const DEPENDENCY_KINDS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+pub fn read_manifest() -> Result<DocumentMut, Box<dyn std::error::Error>> {
+    read_manifest_at(Path::new(MANIFEST_PATH))
+}
+
+pub fn write_manifest(doc: &DocumentMut) -> Result<(), Box<dyn std::error::Error>> {
+    write_manifest_at(Path::new(MANIFEST_PATH), doc)
+}
+
+/// Reads another manifest, such as a workspace member's.
+pub fn read_manifest_at(path: &Path) -> Result<DocumentMut, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content.parse::<DocumentMut>()?)
+}
+
+pub fn write_manifest_at(path: &Path, doc: &DocumentMut) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, doc.to_string())?;
+    Ok(())
+}
+
+/// Runs `f`, then puts Cargo.toml and Cargo.lock back the way they were. Used
+/// by commands that try out temporary changes such as adding a crate.
+pub fn with_restored_manifest<T>(
+    f: impl FnOnce() -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let manifest = fs::read_to_string(MANIFEST_PATH)?;
+    let lockfile = fs::read_to_string(LOCKFILE_PATH).ok();
+
+    let result = f();
+
+    fs::write(MANIFEST_PATH, manifest)?;
+    match lockfile {
+        Some(lockfile) => fs::write(LOCKFILE_PATH, lockfile)?,
+        None => {
+            let _ = fs::remove_file(LOCKFILE_PATH);
+        }
+    }
+
+    result
+}
+
+/// Every dependency table in the manifest, keyed by its section header
+/// (e.g. `dev-dependencies` or `target.'cfg(windows)'.dependencies`).
+pub fn dependency_tables(doc: &DocumentMut) -> Vec<(String, &dyn TableLike)> {
+    let mut tables = Vec::new();
+
+    for kind in DEPENDENCY_KINDS {
+        if let Some(table) = doc.get(kind).and_then(Item::as_table_like) {
+            tables.push((kind.to_string(), table));
+        }
+    }
+
+    if let Some(targets) = doc.get("target").and_then(Item::as_table_like) {
+        for (cfg, target) in targets.iter() {
+            for kind in DEPENDENCY_KINDS {
+                if let Some(table) = target.get(kind).and_then(Item::as_table_like) {
+                    tables.push((format!("target.'{}'.{}", cfg, kind), table));
+                }
+            }
+        }
+    }
+
+    tables
+}
+
+/// The crates.io name of a dependency, honouring `package = "..."` renames.
+pub fn package_name(key: &str, item: &Item) -> String {
+    item.as_table_like()
+        .and_then(|table| table.get("package"))
+        .and_then(Item::as_str)
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Package names of all direct dependencies, across every dependency table.
+pub fn direct_dependencies(doc: &DocumentMut) -> Vec<String> {
+    let mut names: Vec<String> = dependency_tables(doc)
+        .iter()
+        .flat_map(|(_, table)| table.iter().map(|(key, item)| package_name(key, item)))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Mutable counterpart of [`dependency_tables`].
+pub fn dependency_tables_mut(doc: &mut DocumentMut) -> Vec<&mut dyn TableLike> {
+    let mut tables: Vec<&mut dyn TableLike> = Vec::new();
+
+    for (key, item) in doc.as_table_mut().iter_mut() {
+        if DEPENDENCY_KINDS.contains(&key.get()) {
+            if let Some(table) = item.as_table_like_mut() {
+                tables.push(table);
+            }
+        } else if key.get() == "target"
+            && let Some(targets) = item.as_table_like_mut()
+        {
+            for (_, target) in targets.iter_mut() {
+                let Some(target) = target.as_table_like_mut() else {
+                    continue;
+                };
+                for (kind, table) in target.iter_mut() {
+                    if DEPENDENCY_KINDS.contains(&kind.get())
+                        && let Some(table) = table.as_table_like_mut()
+                    {
+                        tables.push(table);
+                    }
+                }
+            }
+        }
+    }
+
+    tables
+}
+
+/// Replaces the version requirement of `package` wherever it is declared.
+/// Returns false if the package isn't a direct dependency.
+pub fn set_dependency_version(doc: &mut DocumentMut, package: &str, req: &str) -> bool {
+    let mut updated = false;
+
+    for table in dependency_tables_mut(doc) {
+        for (key, item) in table.iter_mut() {
+            if package_name(key.get(), item) != package {
+                continue;
+            }
+            match item.as_table_like_mut() {
+                Some(spec) => {
+                    spec.insert("version", value(req));
+                }
+                None => *item = value(req),
+            }
+            updated = true;
+        }
+    }
+
+    updated
+}
+
+/// Names declared in `[features]`, except `default`.
+pub fn feature_names(doc: &DocumentMut) -> Vec<String> {
+    doc.get("features")
+        .and_then(Item::as_table_like)
+        .map(|features| {
+            features
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .filter(|name| name != "default")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a `[features]` list entry turns on the optional dependency `key`.
+/// `key?/feature` only forwards a feature, so it doesn't count.
+pub fn feature_enables(entry: &str, key: &str) -> bool {
+    entry == key
+        || entry.strip_prefix("dep:") == Some(key)
+        || entry.split_once('/').is_some_and(|(dep, _)| dep == key)
+}
+
+/// Whether the optional dependency `key` can be turned on. Unless some
+/// feature refers to it as `dep:key`, cargo creates an implicit feature named
+/// after it, so only `dep:`-referenced dependencies need an enabling feature.
+pub fn optional_dependency_reachable(entries: &[String], key: &str) -> bool {
+    has_implicit_feature(entries, key) || entries.iter().any(|entry| feature_enables(entry, key))
+}
+
+/// Whether `key` is declared with `optional = true` in any dependency table.
+pub fn is_optional_dependency(doc: &DocumentMut, key: &str) -> bool {
+    dependency_tables(doc).iter().any(|(_, table)| {
+        table
+            .get(key)
+            .and_then(Item::as_table_like)
+            .and_then(|spec| spec.get("optional"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false)
+    })
+}
+
+/// Whether cargo creates an implicit feature for the optional dependency `key`.
+pub fn has_implicit_feature(entries: &[String], key: &str) -> bool {
+    !entries
+        .iter()
+        .any(|entry| entry.strip_prefix("dep:") == Some(key))
+}
+
+/// The package's own crate name as written in Rust paths, with hyphens as
+/// underscores. Empty for a virtual manifest.
+pub fn own_crate_name(doc: &DocumentMut) -> String {
+    doc.get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .unwrap_or_default()
+        .replace('-', "_")
+}
+
+/// Every entry of every feature list in `[features]`.
+pub fn feature_entries(doc: &DocumentMut) -> Vec<String> {
+    doc.get("features")
+        .and_then(Item::as_table_like)
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|(_, values)| values.as_array())
+                .flat_map(|values| values.iter().filter_map(|v| v.as_str().map(str::to_string)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
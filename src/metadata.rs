@@ -0,0 +1,54 @@
+use serde_json::Value;
+use std::process::Command;
+
+/// Runs `cargo metadata` and parses its JSON output.
+pub fn cargo_metadata(extra_args: &[&str]) -> Result<Value, Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1"])
+        .args(extra_args)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("cargo metadata failed: {}", stderr.trim()).into());
+    }
+
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// Names of all workspace targets of the given kind (`bin`, `example`, ...).
+pub fn target_names(metadata: &Value, kind: &str) -> Vec<String> {
+    let mut names: Vec<String> = packages(metadata)
+        .iter()
+        .flat_map(|package| package["targets"].as_array().cloned().unwrap_or_default())
+        .filter(|target| {
+            target["kind"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|k| k == kind))
+        })
+        .filter_map(|target| target["name"].as_str().map(str::to_string))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+pub fn packages(metadata: &Value) -> Vec<Value> {
+    metadata["packages"].as_array().cloned().unwrap_or_default()
+}
+
+/// Packages that belong to the workspace, in `workspace_members` order.
+pub fn workspace_packages(metadata: &Value) -> Vec<Value> {
+    let packages = packages(metadata);
+    metadata["workspace_members"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| {
+            packages
+                .iter()
+                .find(|package| package["id"] == *id)
+                .cloned()
+        })
+        .collect()
+}
@@ -0,0 +1,74 @@
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// All `.rs` files under `root`, recursively, skipping build output.
+pub fn rust_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_rust_files(root, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name == "target") {
+                continue;
+            }
+            collect_rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Lines between a function's opening and closing braces. `None` for
+/// declarations without a body, such as trait methods.
+pub fn function_body<'a>(lines: &[&'a str]) -> Option<Vec<&'a str>> {
+    let start = lines
+        .iter()
+        .position(|line| line.contains('{') || line.trim_end().ends_with(';'))?;
+    if !lines[start].contains('{') {
+        return None;
+    }
+
+    let mut depth = 0i32;
+    let mut body = Vec::new();
+    for (offset, line) in lines[start..].iter().enumerate() {
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        if offset > 0 && depth > 0 {
+            body.push(*line);
+        }
+        if depth <= 0 {
+            // A one-line function keeps its body on the signature line
+            if offset == 0 {
+                body.push(lines[start]);
+            }
+            return Some(body);
+        }
+    }
+    None
+}
+
+/// Names declared with `mod` in the given sources. They shadow crates of the
+/// same name, so paths starting with them aren't dependencies.
+pub fn declared_modules<'a>(contents: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+    let mod_regex =
+        Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+([a-zA-Z_][a-zA-Z0-9_]*)").unwrap();
+    contents
+        .into_iter()
+        .flat_map(|content| {
+            mod_regex
+                .captures_iter(content)
+                .map(|cap| cap[1].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}